@@ -0,0 +1,117 @@
+//! Compares the byte-level integer scanner with the previous `&str` based implementation
+//! on an input shaped like day 12: a large JSON document with many small numbers.
+//! Run with `cargo bench --bench int_scanner`, uses `data/inputs/12.txt` if present.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use advent_of_code::utils::scanner::scan_ints;
+
+/// The implementation `IterInts::iter_ints` used before the scanner.
+mod legacy {
+    fn part_of_digit(byte: u8, curr_idx: usize, slice: &[u8]) -> bool {
+        let is_negative_sign =
+            byte == b'-' && slice.get(curr_idx + 1).is_some_and(|b| b.is_ascii_digit());
+
+        is_negative_sign || byte.is_ascii_digit()
+    }
+
+    fn extract_int_indexes(bytes: &[u8]) -> (usize, usize) {
+        let mut start_idx = 0;
+        let mut end_idx = bytes.len();
+        let mut last_char_was_digit = false;
+
+        for (i, b) in bytes.iter().enumerate() {
+            let is_part_of_digit = part_of_digit(*b, i, bytes);
+
+            if is_part_of_digit && !last_char_was_digit {
+                start_idx = i;
+                last_char_was_digit = true;
+            } else if !is_part_of_digit && last_char_was_digit {
+                end_idx = i;
+                break;
+            }
+        }
+        (start_idx, end_idx)
+    }
+
+    fn split_next_int<'a>(slice: &mut &'a str) -> Option<&'a str> {
+        if slice.is_empty() {
+            return None;
+        }
+
+        let (start_idx, end_idx) = extract_int_indexes(slice.as_bytes());
+        let int_str = &slice[start_idx..end_idx];
+        *slice = &slice[end_idx..];
+
+        Some(int_str)
+    }
+
+    pub fn iter_ints(text: &str) -> impl Iterator<Item = i64> + '_ {
+        let mut slice = text;
+        std::iter::from_fn(move || split_next_int(&mut slice)?.parse().ok())
+    }
+}
+
+/// A deterministic JSON document of roughly the size of a day 12 input.
+fn generate_input() -> String {
+    let mut state: u64 = 12;
+    let mut next = move |bound: u64| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) % bound
+    };
+
+    let mut json = String::from("[");
+    for i in 0..2_500 {
+        if i > 0 {
+            json.push(',');
+        }
+        match next(3) {
+            0 => json.push_str(&format!(
+                "{{\"a\":{},\"b\":\"red\",\"c\":[{},-{}]}}",
+                next(200),
+                next(50),
+                next(50)
+            )),
+            1 => json.push_str(&format!("[{},\"blue\",{}]", next(1000), next(10))),
+            _ => json.push_str(&format!("{{\"d\":-{},\"e\":\"violet\"}}", next(100))),
+        }
+    }
+    json.push(']');
+    json
+}
+
+fn measure(name: &str, iterations: u32, mut func: impl FnMut() -> i64) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(func());
+    }
+    let per_iteration = start.elapsed() / iterations;
+    println!("{name:>8}: {per_iteration:?} per iteration");
+    per_iteration
+}
+
+fn main() {
+    let input = std::fs::read_to_string("data/inputs/12.txt").unwrap_or_else(|_| generate_input());
+    let iterations = 500;
+
+    assert_eq!(
+        legacy::iter_ints(&input).sum::<i64>(),
+        scan_ints::<i64>(input.as_bytes()).sum::<i64>()
+    );
+
+    println!("Scanning {} bytes for integers", input.len());
+    let before = measure("legacy", iterations, || {
+        legacy::iter_ints(black_box(&input)).sum()
+    });
+    let after = measure("scanner", iterations, || {
+        scan_ints::<i64>(black_box(input.as_bytes())).sum()
+    });
+
+    println!(
+        "speedup: {:.2}x",
+        before.as_secs_f64() / after.as_secs_f64()
+    );
+}
@@ -0,0 +1,231 @@
+//! Compares the JSON fold with the hand-rolled state machine day 12 used before,
+//! summing all numbers outside of objects with a `"red"` value.
+//! Run with `cargo bench --bench json`, uses `data/inputs/12.txt` if present.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use advent_of_code::utils::json;
+
+/// The implementation of day 12 part two before the JSON fold.
+mod legacy {
+    struct RedFinder {
+        buf: [char; 6],
+        idx: usize,
+    }
+
+    impl RedFinder {
+        pub fn new() -> Self {
+            RedFinder {
+                buf: [0 as char; 6],
+                idx: 0,
+            }
+        }
+        pub fn process_char(&mut self, c: char) -> bool {
+            match self.idx {
+                0 if c == ':' => self.assign_and_advance(c),
+                1 if c == '"' => self.assign_and_advance(c),
+                2 if c == 'r' => self.assign_and_advance(c),
+                3 if c == 'e' => self.assign_and_advance(c),
+                4 if c == 'd' => self.assign_and_advance(c),
+                5 if c == '"' => {
+                    self.reset();
+                    return true;
+                }
+                _ => self.reset(),
+            }
+
+            false
+        }
+
+        fn assign_and_advance(&mut self, c: char) {
+            self.buf[self.idx] = c;
+            self.idx += 1
+        }
+
+        fn reset(&mut self) {
+            self.idx = 0
+        }
+    }
+
+    enum ObjectContext {
+        Root,
+        Child(usize),
+        Out,
+    }
+
+    enum BadObject {
+        None,
+        Found(usize),
+    }
+
+    pub fn part_two(input: &str) -> Option<i64> {
+        let mut result = 0;
+        let mut buf = String::with_capacity(10);
+        let mut oc = ObjectContext::Out;
+        let mut in_object_sums = [0; 100];
+        let mut red_finder = RedFinder::new();
+        let mut object_contrib = 0;
+        let mut bad_object = BadObject::None;
+
+        for c in input.chars() {
+            if c == '{' {
+                oc = match oc {
+                    ObjectContext::Root => {
+                        in_object_sums[0] += object_contrib;
+                        ObjectContext::Child(1)
+                    }
+                    ObjectContext::Child(depth) => {
+                        in_object_sums[depth] += object_contrib;
+                        ObjectContext::Child(depth + 1)
+                    }
+                    ObjectContext::Out => ObjectContext::Root,
+                };
+
+                object_contrib = 0;
+                continue;
+            }
+
+            match c {
+                '-' => buf.push(c),
+                '0'..='9' => buf.push(c),
+                _ => {
+                    if matches!(bad_object, BadObject::None) && red_finder.process_char(c) {
+                        bad_object = match oc {
+                            ObjectContext::Root => BadObject::Found(0),
+                            ObjectContext::Child(depth) => BadObject::Found(depth),
+                            _ => unreachable!("Bad object found in non-object context"),
+                        };
+                        object_contrib = 0;
+                        buf.clear();
+                        continue;
+                    }
+
+                    if !buf.is_empty()
+                        && let Ok(num) = buf.parse::<i64>()
+                    {
+                        match oc {
+                            ObjectContext::Out => result += num,
+                            _ => object_contrib += num,
+                        }
+
+                        buf.clear();
+                    }
+                }
+            }
+
+            if c == '}' {
+                oc = match oc {
+                    ObjectContext::Root => {
+                        if let BadObject::None = bad_object {
+                            result += in_object_sums[0] + object_contrib;
+                        } else {
+                            bad_object = BadObject::None;
+                        }
+
+                        in_object_sums[0] = 0;
+
+                        ObjectContext::Out
+                    }
+                    ObjectContext::Child(depth) => {
+                        if let BadObject::Found(bad_object_depth) = bad_object {
+                            if bad_object_depth == depth {
+                                bad_object = BadObject::None;
+                            }
+                        } else {
+                            in_object_sums[depth - 1] += object_contrib + in_object_sums[depth];
+                        }
+
+                        in_object_sums[depth] = 0;
+
+                        if depth == 1 {
+                            ObjectContext::Root
+                        } else {
+                            ObjectContext::Child(depth - 1)
+                        }
+                    }
+                    _ => panic!("Open bracket not found, bad input."),
+                };
+
+                object_contrib = 0;
+            }
+        }
+
+        Some(result)
+    }
+}
+
+/// A deterministic JSON document of roughly the size of a day 12 input.
+fn generate_input() -> String {
+    let mut state: u64 = 12;
+    let mut next = move |bound: u64| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) % bound
+    };
+
+    let mut json = String::from("[");
+    for i in 0..2_500 {
+        if i > 0 {
+            json.push(',');
+        }
+        match next(3) {
+            0 => json.push_str(&format!(
+                "{{\"a\":{},\"b\":\"red\",\"c\":[{},-{}]}}",
+                next(200),
+                next(50),
+                next(50)
+            )),
+            1 => json.push_str(&format!("[{},\"blue\",{}]", next(1000), next(10))),
+            _ => json.push_str(&format!("{{\"d\":-{},\"e\":\"violet\"}}", next(100))),
+        }
+    }
+    json.push(']');
+    json
+}
+
+fn fold(input: &str) -> i64 {
+    json::fold(
+        input,
+        |key, value| key.is_some() && value.is_str("red"),
+        |value| value.as_i64().unwrap_or(0),
+        |a, b| a + b,
+    )
+    .unwrap()
+}
+
+fn measure(iterations: u32, mut func: impl FnMut() -> i64) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(func());
+    }
+    start.elapsed() / iterations
+}
+
+fn main() {
+    let input = std::fs::read_to_string("data/inputs/12.txt").unwrap_or_else(|_| generate_input());
+    let input = input.trim();
+    let (rounds, iterations) = (10, 200);
+
+    assert_eq!(legacy::part_two(input), Some(fold(input)));
+
+    // the rounds alternate between both, and the fastest round of each is compared,
+    // which keeps noise from other processes out of the result.
+    let (mut before, mut after) = (Duration::MAX, Duration::MAX);
+    for _ in 0..rounds {
+        before = before.min(measure(iterations, || {
+            legacy::part_two(black_box(input)).unwrap()
+        }));
+        after = after.min(measure(iterations, || fold(black_box(input))));
+    }
+
+    println!("Summing {} bytes of JSON", input.len());
+    println!("  legacy: {before:?} per iteration");
+    println!("    fold: {after:?} per iteration");
+
+    println!(
+        "speedup: {:.2}x",
+        before.as_secs_f64() / after.as_secs_f64()
+    );
+}
@@ -1,63 +1,46 @@
-use std::collections::HashSet;
+use advent_of_code::utils::coord_2d::{Coord, Direction};
+use advent_of_code::utils::sparse_grid::SparseGrid;
 
 advent_of_code::solution!(3);
 
-#[derive(Clone, Copy, Hash, Eq, PartialEq)]
-struct Coord {
-    pub x: i32,
-    pub y: i32,
+fn parse_directions(input: &str) -> impl Iterator<Item = Direction> + '_ {
+    input.chars().filter_map(|c| Direction::try_from(c).ok())
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut location = Coord { x: 0, y: 0 };
-    let mut set = HashSet::new();
-    set.insert(location);
-    let mut count = 1;
+    let mut location = Coord::ORIGIN;
+    let mut visits = SparseGrid::<u32>::new();
+    *visits.get_or_insert_default(location) += 1;
 
-    for c in input.chars() {
-        match c {
-            '^' => location.y += 1,
-            'v' => location.y -= 1,
-            '>' => location.x += 1,
-            '<' => location.x -= 1,
-            _ => {}
-        }
-
-        count += set.insert(location) as usize;
+    for direction in parse_directions(input) {
+        location += direction;
+        *visits.get_or_insert_default(location) += 1;
     }
 
-    Some(count)
+    Some(visits.len())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut location_santa = Coord { x: 0, y: 0 };
-    let mut location_robot_santa = Coord { x: 0, y: 0 };
-    let mut set = HashSet::new();
-    set.insert(location_santa);
-    let mut count = 1;
+    let mut location_santa = Coord::ORIGIN;
+    let mut location_robot_santa = Coord::ORIGIN;
+    let mut visits = SparseGrid::<u32>::new();
+    *visits.get_or_insert_default(location_santa) += 2;
     let mut santa_turn = true;
 
-    for c in input.chars() {
+    for direction in parse_directions(input) {
         let location = if santa_turn {
             &mut location_santa
         } else {
             &mut location_robot_santa
         };
 
-        match c {
-            '^' => location.y += 1,
-            'v' => location.y -= 1,
-            '>' => location.x += 1,
-            '<' => location.x -= 1,
-            _ => {}
-        }
-
-        count += set.insert(*location) as usize;
+        *location += direction;
+        *visits.get_or_insert_default(*location) += 1;
 
         santa_turn = !santa_turn;
     }
 
-    Some(count)
+    Some(visits.len())
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use advent_of_code::utils::coord_2d::Coord;
 use advent_of_code::utils::geom::PositionalRectangle;
-use advent_of_code::utils::grid::Grid;
+use advent_of_code::utils::regions::CompressedGrid;
 use std::str::FromStr;
 
 advent_of_code::solution!(6);
@@ -12,9 +12,6 @@ enum Action {
     Unknown,
 }
 
-type BigBoolGrid = Grid<bool, 1_000_000>;
-type BigIntGrid = Grid<i32, 1_000_000>;
-
 fn parse_action_and_rectangle(instruction: &str) -> (Action, PositionalRectangle) {
     let mut element = instruction.split(' ');
 
@@ -43,52 +40,40 @@ fn parse_action_and_rectangle(instruction: &str) -> (Action, PositionalRectangle
     (action, rect)
 }
 
+fn parse_instructions(input: &str) -> Vec<(PositionalRectangle, Action)> {
+    input
+        .lines()
+        .map(|instruction| {
+            let (action, rect) = parse_action_and_rectangle(instruction);
+            (rect, action)
+        })
+        .collect()
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut grid = BigBoolGrid::new_boxed();
-
-    for instruction in input.lines() {
-        let (action, rect) = parse_action_and_rectangle(instruction);
-
-        match action {
-            Action::TurnOn => {
-                grid.fill_in_rectangle(rect, true);
-            }
-            Action::TurnOff => {
-                grid.fill_in_rectangle(rect, false);
-            }
-            Action::Toggle => {
-                grid.toggle_in_rectangle(rect);
-            }
-            Action::Unknown => {
-                unreachable!("Bad input")
-            }
-        }
-    }
+    let instructions = parse_instructions(input);
 
-    Some(grid.count_on())
+    let grid = CompressedGrid::<bool>::evaluate(&instructions, |light, action| match action {
+        Action::TurnOn => *light = true,
+        Action::TurnOff => *light = false,
+        Action::Toggle => *light = !*light,
+        Action::Unknown => unreachable!("Bad input"),
+    });
+
+    Some(grid.count_where(|light| *light))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut grid = BigIntGrid::new_boxed();
-
-    for instruction in input.lines() {
-        let (action, rect) = parse_action_and_rectangle(instruction);
-
-        match action {
-            Action::TurnOn => grid.apply_in_rectangle(rect, |light| *light += 1),
-            Action::TurnOff => grid.apply_in_rectangle(rect, |light| {
-                if *light > 0 {
-                    *light -= 1
-                }
-            }),
-            Action::Toggle => grid.apply_in_rectangle(rect, |light| *light += 2),
-            Action::Unknown => {
-                unreachable!("Bad input")
-            }
-        }
-    }
+    let instructions = parse_instructions(input);
+
+    let grid = CompressedGrid::<usize>::evaluate(&instructions, |light, action| match action {
+        Action::TurnOn => *light += 1,
+        Action::TurnOff => *light = light.saturating_sub(1),
+        Action::Toggle => *light += 2,
+        Action::Unknown => unreachable!("Bad input"),
+    });
 
-    Some(grid.iter().map(|it| it.value as usize).sum())
+    Some(grid.sum_by(|light| *light))
 }
 
 #[cfg(test)]
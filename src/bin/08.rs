@@ -1,56 +1,25 @@
-use itertools::Itertools;
+use advent_of_code::utils::escape::{EscapeSet, escaped_len, unescaped_len};
 
 advent_of_code::solution!(8);
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut total_size = 0;
-    
-    for line in input.lines() {
-        let internal_str = &line[1..line.len() - 1];
-        let code_size = internal_str.len() + 2;
-        let mut in_memory_string_size = 0;
-    
-        let mut chars_it = internal_str.chars();
-    
-        while let Some(c) = chars_it.next() {
-            if c == '\\'
-                && let Some(next_c) = chars_it.next()
-            {
-                chars_it = chars_it.dropping(match next_c {
-                    // we skip 0 times except for 'x' as we already advanced the iterator
-                    // once to check the next character
-                    '\\' => 0,
-                    '"' => 0,
-                    'x' => 2,
-                    // input is guaranteed to contain only the cases above
-                    _ => unreachable!("Bad input, unknow escape sequence"),
-                });
-            }
-    
-            in_memory_string_size += 1;
-        }
-    
-        total_size += code_size - in_memory_string_size;
-    }
-    
-    Some(total_size)
+    Some(
+        input
+            .lines()
+            .map(|line| {
+                let memory_size = unescaped_len(line, EscapeSet::Basic)
+                    .unwrap_or_else(|e| panic!("Bad input, {e}"));
+                line.len() - memory_size
+            })
+            .sum(),
+    )
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     Some(
         input
             .lines()
-            .map(|line| &line[1..line.len() - 1])
-            .map(|s| {
-                s.chars()
-                    .map(|c| match c {
-                        '\\' => 1,
-                        '"' => 1,
-                        _ => 0,
-                    })
-                    .sum::<usize>()
-                    + 4
-            })
+            .map(|line| escaped_len(line.as_bytes(), EscapeSet::Basic) - line.len())
             .sum(),
     )
 }
@@ -1,112 +1,33 @@
-use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use advent_of_code::utils::parsing::UnwrapNextInt;
+use advent_of_code::utils::graph::{Goal, Graph};
+use advent_of_code::utils::parsing::{Pattern, PatternError};
 
 advent_of_code::solution!(9);
 
-type City<'a> = &'a str;
-type Path<'a> = (City<'a>, u64);
-type Neighbors<'a> = HashSet<Path<'a>>;
-type Map<'a> = HashMap<City<'a>, Neighbors<'a>>;
+fn parse_graph(input: &str) -> Result<Graph<'_, u64>, PatternError> {
+    let mut graph = Graph::new();
+    let pattern = Pattern::new("{} to {} = {}");
 
-struct Graph<'a> {
-    _map: Map<'a>,
-}
-
-#[derive(Copy, Clone)]
-enum VisitOrder {
-    ShortestToLongest,
-    LongestToShortest,
-}
-
-impl<'a> Graph<'a> {
-    fn parse(input: &'a str) -> Graph<'a> {
-        let mut graph = Map::new();
-
-        for line in input.lines() {
-            let mut split = line.split(' ');
-            let start_city = split.next().unwrap();
-            split.next(); // skip "to"
-            let end_city = split.next().unwrap();
-            split.next(); // skip "="
-            let distance = split.unwrap_next_int();
-
-            Self::add_path(start_city, end_city, distance, &mut graph);
-            Self::add_path(end_city, start_city, distance, &mut graph);
-        }
-
-        Graph { _map: graph }
+    for line in input.lines() {
+        let (start_city, end_city, distance) = pattern.parse(line)?;
+        let (start, end) = (graph.node(start_city), graph.node(end_city));
+        graph.add_undirected_edge(start, end, distance);
     }
 
-    fn add_path(from: City<'a>, to: City<'a>, dist: u64, map: &mut Map<'a>) {
-        if let Some(neighbours) = map.get_mut(from) {
-            neighbours.insert((to, dist));
-        } else {
-            let mut neighbours = HashSet::new();
-            neighbours.insert((to, dist));
-            map.insert(from, neighbours);
-        }
-    }
-
-    fn solve(&self, order: VisitOrder) -> u64 {
-        let candidate_distances = self
-            ._map
-            .keys()
-            .map(|starting_city| self.visit(starting_city, &mut vec![], 0, order));
-
-        match order {
-            VisitOrder::ShortestToLongest => candidate_distances.min(),
-            VisitOrder::LongestToShortest => candidate_distances.max(),
-        }
-        .unwrap()
-    }
-
-    fn visit(
-        &self,
-        city: City<'a>,
-        visited: &mut Vec<City<'a>>,
-        distance_acc: u64,
-        order: VisitOrder,
-    ) -> u64 {
-        visited.push(city);
-
-        if visited.len() == self._map.len() {
-            return distance_acc;
-        }
-
-        let next_neighbours = self._map[city]
-            .iter()
-            .filter(|(neighbour_city, _)| !visited.contains(neighbour_city));
-
-        let comparison_func =
-            |(_, distance_a): &&Path, (_, distance_b): &&Path| distance_a.cmp(distance_b);
+    Ok(graph)
+}
 
-        let (next_city, distance_to_next_city) = match order {
-            VisitOrder::ShortestToLongest => next_neighbours.k_smallest_by(1, comparison_func),
-            VisitOrder::LongestToShortest => next_neighbours.k_largest_by(1, comparison_func),
-        }
-        .next()
-        .unwrap();
+fn solve(input: &str, goal: Goal) -> Option<u64> {
+    let graph = parse_graph(input).unwrap_or_else(|e| panic!("Bad input, {e}"));
 
-        self.visit(
-            next_city,
-            visited,
-            distance_acc + distance_to_next_city,
-            order,
-        )
-    }
+    Some(graph.hamiltonian_path(goal)?.cost)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let graph = Graph::parse(input);
-
-    Some(graph.solve(VisitOrder::ShortestToLongest))
+    solve(input, Goal::Minimize)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let graph = Graph::parse(input);
-
-    Some(graph.solve(VisitOrder::LongestToShortest))
+    solve(input, Goal::Maximize)
 }
 
 #[cfg(test)]
@@ -1,124 +1,51 @@
-use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use advent_of_code::utils::parsing::UnwrapNextInt;
+use advent_of_code::utils::graph::{Goal, Graph};
+use advent_of_code::utils::parsing::{Pattern, PatternError};
 
 advent_of_code::solution!(13);
 
-type Pair<'a> = (&'a str, &'a str);
-type GuestList<'a> = HashSet<&'a str>;
-type GuestAffinity<'a> = HashMap<Pair<'a>, i64>;
-
-fn parse_guest_list(input: &str) -> (GuestList<'_>, GuestAffinity<'_>) {
-    let mut guest_list = HashSet::<&str>::new();
-    let mut affinities = GuestAffinity::new();
+/// Guests are nodes, the weight between two guests is the happiness both gain sitting next to each other.
+fn parse_guest_list(input: &str) -> Result<Graph<'_, i64>, PatternError> {
+    let mut graph = Graph::new();
+    let pattern = Pattern::new("{} would {} {} happiness units by sitting next to {}.");
 
     for line in input.lines() {
-        let mut split = line.split_ascii_whitespace();
-
-        let guest1 = split.next().unwrap();
-
-        split.next(); // "would"
+        let (guest1, verb, amount, guest2): (&str, &str, i64, &str) = pattern.parse(line)?;
 
-        let sign = match split.next().unwrap() {
+        let sign = match verb {
             "gain" => 1,
             "lose" => -1,
             _ => unreachable!("Bad verb"),
         };
 
-        let value = split.unwrap_next_int::<i64>() * sign;
-
-        for _ in 0..6 {
-            split.next(); // "happiness units by sitting next to"
-        }
-
-        let guest2 = split.next().unwrap().strip_suffix('.').unwrap();
-
-        let affinity_ab = affinities.get(&(guest1, guest2));
-        let affinity_ba = affinities.get(&(guest2, guest1));
-
-        _ = match (affinity_ab, affinity_ba) {
-            (None, None) => affinities.insert((guest1, guest2), value),
-            (None, Some(ba)) => affinities.insert((guest2, guest1), ba + value),
-            _ => None,
-        };
-
-        guest_list.insert(guest1);
+        let (a, b) = (graph.node(guest1), graph.node(guest2));
+        let affinity = graph.weight(a, b).unwrap_or(0) + amount * sign;
+        graph.add_undirected_edge(a, b, affinity);
     }
 
-    (guest_list, affinities)
+    Ok(graph)
 }
 
-fn calculate_affinity_for_arrangement(
-    guest: &str,
-    guest_list: &GuestList<'_>,
-    affinities: &GuestAffinity<'_>,
-) -> i64 {
-    let mut next_guest = guest;
-    let mut seated_guests = Vec::with_capacity(guest_list.len());
-    let mut total_affinity = 0;
-
-    while seated_guests.len() < guest_list.len() {
-        if let Some(next_best_pair) = affinities
-            .iter()
-            .filter(|((g1, g2), _)| {
-                *g1 == next_guest && !seated_guests.contains(g2)
-                    || (*g2 == next_guest && !seated_guests.contains(g1))
-            })
-            .k_largest_by_key(1, |(_, affinity)| *affinity)
-            .next()
-        {
-            let ((g1, g2), affinity) = next_best_pair;
-            if *g1 == next_guest {
-                seated_guests.push(g1);
-                next_guest = g2;
-            } else {
-                seated_guests.push(g2);
-                next_guest = g1;
-            }
-
-            total_affinity += affinity;
-        } else {
-            break;
-        }
-    }
-
-    total_affinity += affinities
-        .get(&(guest, next_guest))
-        .or_else(|| affinities.get(&(next_guest, guest)))
-        .unwrap();
-
-    total_affinity
+fn best_arrangement(graph: &Graph<'_, i64>) -> Option<i64> {
+    Some(graph.hamiltonian_cycle(Goal::Maximize)?.cost)
 }
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let (guest_list, affinities) = parse_guest_list(input);
+    let graph = parse_guest_list(input).unwrap_or_else(|e| panic!("Bad input, {e}"));
 
-    Some(
-        guest_list
-            .iter()
-            .map(|guest| calculate_affinity_for_arrangement(guest, &guest_list, &affinities))
-            .max()
-            .unwrap(),
-    )
+    best_arrangement(&graph)
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let (mut guest_list, mut affinities) = parse_guest_list(input);
-    let me = "Me";
+    let mut graph = parse_guest_list(input).unwrap_or_else(|e| panic!("Bad input, {e}"));
+    let me = graph.node("Me");
 
-    guest_list.insert(me);
-
-    for guest in &guest_list {
-        affinities.insert((me, *guest), 0);
+    for guest in 0..graph.len() {
+        if guest != me {
+            graph.add_undirected_edge(me, guest, 0);
+        }
     }
 
-    Some(
-        guest_list
-            .iter()
-            .map(|guest| calculate_affinity_for_arrangement(guest, &guest_list, &affinities))
-            .max()
-            .unwrap(),
-    )
+    best_arrangement(&graph)
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
-use advent_of_code::utils::parsing::IterInts;
-use itertools::Itertools;
+use advent_of_code::utils::combinatorics::compositions;
+use advent_of_code::utils::parsing::{FromRecord, Record, RecordError, parse_records};
 
 advent_of_code::solution!(15);
 
@@ -11,35 +11,25 @@ struct Ingredient {
     calories: i64,
 }
 
-impl Ingredient {
-    fn from_str(text: &str) -> Option<Ingredient> {
-        text.iter_ints()
-            .collect_tuple()
-            .map(
-                |(capacity, durability, flavor, texture, calories)| Ingredient {
-                    capacity,
-                    durability,
-                    flavor,
-                    texture,
-                    calories,
-                },
-            )
+impl FromRecord<'_> for Ingredient {
+    fn from_record(record: &Record<'_>) -> Result<Self, RecordError> {
+        Ok(Ingredient {
+            capacity: record.int("capacity")?,
+            durability: record.int("durability")?,
+            flavor: record.int("flavor")?,
+            texture: record.int("texture")?,
+            calories: record.int("calories")?,
+        })
     }
 }
 
 fn parse_ingredients(input: &str) -> Vec<Ingredient> {
-    let mut ingredients = Vec::new();
-
-    for line in input.lines() {
-        ingredients.push(Ingredient::from_str(line).unwrap())
-    }
-
-    ingredients
+    parse_records(input).unwrap_or_else(|e| panic!("Bad input, {e}"))
 }
 
 fn calculate_total_score(
     ingredients: &[Ingredient],
-    teaspoons: &[i64],
+    teaspoons: &[u32],
     target_calories: Option<i64>,
 ) -> i64 {
     let (capacity_score, durability_score, texture_score, flavor_score, total_calories) =
@@ -47,6 +37,7 @@ fn calculate_total_score(
             .iter()
             .zip(teaspoons)
             .fold((0, 0, 0, 0, 0), |(c, d, t, f, ca), (ig, &ts)| {
+                let ts = i64::from(ts);
                 (
                     c + ig.capacity * ts,
                     d + ig.durability * ts,
@@ -66,12 +57,9 @@ fn calculate_total_score(
 }
 
 fn solve(ingredients: &[Ingredient], target_calories: Option<i64>) -> Option<i64> {
-    const TOTAL_INGREDIENTS: i64 = 100;
+    const TOTAL_INGREDIENTS: u32 = 100;
 
-    (0..ingredients.len())
-        .map(|_| 0..TOTAL_INGREDIENTS)
-        .multi_cartesian_product()
-        .filter(|teaspoons| teaspoons.iter().sum::<i64>() == TOTAL_INGREDIENTS)
+    compositions(TOTAL_INGREDIENTS, ingredients.len())
         .map(|teaspoons| calculate_total_score(ingredients, &teaspoons, target_calories))
         .max()
 }
@@ -1,4 +1,6 @@
-use advent_of_code::utils::parsing::IterInts;
+use advent_of_code::utils::parsing::{
+    FromRecord, Record, RecordError, RecordErrorKind, parse_records,
+};
 
 advent_of_code::solution!(16);
 
@@ -53,22 +55,44 @@ const MESSAGE: [i64; 10] = {
     msg
 };
 
+struct Sue {
+    number: i64,
+    compounds: Vec<(Compound, i64)>,
+}
+
+impl FromRecord<'_> for Sue {
+    fn from_record(record: &Record<'_>) -> Result<Self, RecordError> {
+        let compounds = record
+            .fields
+            .iter()
+            .map(|(name, _)| {
+                let compound = Compound::from_name(name)
+                    .ok_or_else(|| record.error(RecordErrorKind::UnknownField(name.to_string())))?;
+                Ok((compound, record.int(name)?))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Sue {
+            number: record.id_number()?,
+            compounds,
+        })
+    }
+}
+
+fn parse_sues(input: &str) -> Vec<Sue> {
+    parse_records(input).unwrap_or_else(|e| panic!("Bad input, {e}"))
+}
+
 pub fn part_one(input: &str) -> Option<i64> {
-    for line in input.lines() {
-        let mut it = line.iter_named_ints::<i64>();
-        let mut matches = 0;
-        if let Some((_, sue_num)) = it.next() {
-            for (compound_name, amount) in it {
-                if let Some(compound) = Compound::from_name(compound_name)
-                    && MESSAGE[compound as usize] == amount
-                {
-                    matches += 1
-                }
-            }
-
-            if matches == 3 {
-                return Some(sue_num);
-            }
+    for sue in parse_sues(input) {
+        let matches = sue
+            .compounds
+            .iter()
+            .filter(|(compound, amount)| MESSAGE[*compound as usize] == *amount)
+            .count();
+
+        if matches == 3 {
+            return Some(sue.number);
         }
     }
 
@@ -76,25 +100,20 @@ pub fn part_one(input: &str) -> Option<i64> {
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    for line in input.lines() {
-        let mut it = line.iter_named_ints::<i64>();
+    for sue in parse_sues(input) {
         let mut matches = 0;
-        if let Some((_, sue_num)) = it.next() {
-            for (compound_name, amount) in it {
-                if let Some(compound) = Compound::from_name(compound_name) {
-                    let message_amount = MESSAGE[compound as usize];
-                    use Compound::*;
-                    matches += match compound {
-                        Cats | Trees => amount > message_amount,
-                        Pomeranians | Goldfish => amount < message_amount,
-                        _ => amount == message_amount,
-                    } as i32
-                }
-            }
-
-            if matches == 3 {
-                return Some(sue_num);
-            }
+        for &(compound, amount) in &sue.compounds {
+            let message_amount = MESSAGE[compound as usize];
+            use Compound::*;
+            matches += match compound {
+                Cats | Trees => amount > message_amount,
+                Pomeranians | Goldfish => amount < message_amount,
+                _ => amount == message_amount,
+            } as i32
+        }
+
+        if matches == 3 {
+            return Some(sue.number);
         }
     }
 
@@ -8,11 +8,13 @@ use std::process;
 
 mod args {
     use advent_of_code::template::Day;
+    use advent_of_code::template::report::{Format, ReportOptions, ReportTarget};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
@@ -27,6 +29,8 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            watch: bool,
+            test: bool,
         },
         All {
             release: bool,
@@ -35,6 +39,8 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            target: ReportTarget,
+            options: ReportOptions,
         },
         #[cfg(feature = "today")]
         Today,
@@ -51,14 +57,34 @@ mod args {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
 
+                let mut target = ReportTarget::default();
+                if let Some(path) = args.opt_value_from_str("--report")? {
+                    target.path = path;
+                }
+                if let Some(marker) = args.opt_value_from_str("--marker")? {
+                    target.marker = marker;
+                }
+
+                let format: Option<Format> = args.opt_value_from_str("--format")?;
+                let options = ReportOptions {
+                    format: format
+                        .or_else(|| Format::from_path(&target.path))
+                        .unwrap_or_default(),
+                    sort: args.opt_value_from_str("--sort")?.unwrap_or_default(),
+                    chart: args.opt_value_from_str("--chart")?,
+                };
+
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    target,
+                    options,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
@@ -73,6 +99,8 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                watch: args.contains("--watch"),
+                test: args.contains("--test"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -103,8 +131,14 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                target,
+                options,
+            } => time::handle(day, all, store, &target, &options),
+            AppArguments::Download { day, force } => download::handle(day, force),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
@@ -113,7 +147,7 @@ fn main() {
             } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, false);
                 }
             }
             AppArguments::Solve {
@@ -121,13 +155,15 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                test,
+            } => solve::handle(day, release, dhat, submit, watch, test),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
                         scaffold::handle(day, false);
-                        download::handle(day);
+                        download::handle(day, false);
                         read::handle(day)
                     }
                     None => {
@@ -0,0 +1,185 @@
+/// Counts heap allocations of solutions, so that they can be reported next to the timings.
+/// Solution binaries install [`CountingAlloc`] as global allocator, unless DHAT is used.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::hint::black_box;
+use std::ops::Add;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Set by [`CountingAlloc`] on its first allocation.
+const INSTALLED: u8 = 1;
+/// Set by [`measure`] while it runs a solution.
+const COUNTING: u8 = 2;
+static STATE: AtomicU8 = AtomicU8::new(0);
+
+/// The system allocator, counting every allocation and the bytes requested while [`measure`]
+/// runs. A reallocation counts as a new allocation of the new size.
+///
+/// Outside of [`measure`], e.g. while benchmarking, the only overhead is loading one atomic
+/// flag per allocation.
+pub struct CountingAlloc;
+
+fn count(size: usize) {
+    let state = STATE.load(Ordering::Relaxed);
+    if state & COUNTING != 0 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    }
+    if state & INSTALLED == 0 {
+        STATE.fetch_or(INSTALLED, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+/// Heap allocations of a single run of a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+impl AllocStats {
+    fn now() -> Self {
+        AllocStats {
+            count: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Parse stats formatted by [`AllocStats::to_output`], e.g. `12 allocs, 3456 bytes`.
+    pub fn from_output(s: &str) -> Option<Self> {
+        let (count, bytes) = s.split_once(", ")?;
+        Some(AllocStats {
+            count: count.strip_suffix(" allocs")?.parse().ok()?,
+            bytes: bytes.strip_suffix(" bytes")?.parse().ok()?,
+        })
+    }
+
+    /// Machine readable stats, as printed by solution runs.
+    pub fn to_output(self) -> String {
+        format!("{} allocs, {} bytes", self.count, self.bytes)
+    }
+}
+
+impl Add for AllocStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        AllocStats {
+            count: self.count + other.count,
+            bytes: self.bytes + other.bytes,
+        }
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut size = self.bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            write!(f, "{} allocs ({} B)", self.count, self.bytes)
+        } else {
+            write!(f, "{} allocs ({size:.1} {})", self.count, UNITS[unit])
+        }
+    }
+}
+
+/// Runs `func` and counts its allocations. `None` if [`CountingAlloc`] is not installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    // an installed allocator marks itself on its first allocation.
+    drop(black_box(Box::new(0u8)));
+
+    let before = AllocStats::now();
+    STATE.fetch_or(COUNTING, Ordering::Relaxed);
+    let result = func();
+    STATE.fetch_and(!COUNTING, Ordering::Relaxed);
+    let after = AllocStats::now();
+
+    let installed = STATE.load(Ordering::Relaxed) & INSTALLED != 0;
+    let stats = installed.then(|| AllocStats {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+    });
+    (result, stats)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout};
+
+    use super::{AllocStats, CountingAlloc, measure};
+
+    #[test]
+    fn counts_allocations() {
+        // the allocator is not installed in tests, so only the calls below are counted.
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let (_, stats) = measure(|| unsafe {
+            let ptr = CountingAlloc.alloc(layout);
+            let ptr = CountingAlloc.realloc(ptr, layout, 128);
+            CountingAlloc.dealloc(ptr, Layout::from_size_align(128, 8).unwrap());
+        });
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                count: 2,
+                bytes: 192
+            })
+        );
+
+        // not allocating is not mistaken for a missing allocator.
+        assert_eq!(measure(|| ()).1, Some(AllocStats::default()));
+
+        // allocations outside of `measure` are not counted.
+        let before = AllocStats::now();
+        unsafe { CountingAlloc.dealloc(CountingAlloc.alloc(layout), layout) };
+        assert_eq!(AllocStats::now(), before);
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = AllocStats {
+            count: 3,
+            bytes: 276,
+        };
+        assert_eq!(stats.to_string(), "3 allocs (276 B)");
+        assert_eq!(AllocStats::from_output(&stats.to_output()), Some(stats));
+
+        let large = AllocStats {
+            count: 1200,
+            bytes: 3 * 1024 * 1024 + 512 * 1024,
+        };
+        assert_eq!(large.to_string(), "1200 allocs (3.5 MiB)");
+        assert_eq!(AllocStats::from_output("3 allocs"), None);
+    }
+}
@@ -1,7 +1,31 @@
+use crate::template::input_cache::{self, Manifest};
 use crate::template::{Day, aoc_cli};
+use std::path::Path;
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(day: Day, force: bool) {
+    let valid_input = input_cache::read_valid_input(day).ok();
+    let has_valid_input = valid_input.is_some();
+    let has_puzzle = Path::new(&aoc_cli::get_puzzle_path(day)).exists();
+
+    let mut manifest = Manifest::read_from_file();
+
+    if !force && has_valid_input && has_puzzle {
+        println!(
+            "🎄 Input and puzzle for day {day} are already present. Use `--force` to download them again."
+        );
+
+        // keep the recorded checksum of a changed input, so running the day still warns about it.
+        if let Some(input) = valid_input
+            && manifest.is_input_changed(day, &input)
+        {
+            eprintln!("Warning: input for day {day} changed since the last download.");
+        } else {
+            record(&mut manifest, day);
+        }
+        return;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!(
             "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
@@ -9,8 +33,32 @@ pub fn handle(day: Day) {
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    // keep a valid input around unless asked otherwise, only the puzzle is missing.
+    let result = if !force && has_valid_input {
+        aoc_cli::download_puzzle(day)
+    } else {
+        aoc_cli::download(day)
+    };
+
+    if let Err(e) = result {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    match input_cache::read_valid_input(day) {
+        Ok(input) if manifest.is_input_changed(day, &input) => {
+            eprintln!("Warning: input for day {day} changed since the last download.");
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Warning: downloaded {e}"),
+    }
+
+    record(&mut manifest, day);
+}
+
+fn record(manifest: &mut Manifest, day: Day) {
+    manifest.record(day);
+    if let Err(e) = manifest.store_file() {
+        eprintln!("Failed to store checksum manifest: {e}");
+    }
 }
@@ -1,17 +1,43 @@
-use std::process;
+use std::{fs, path::Path, process};
 
-use crate::template::{Day, aoc_cli};
+use crate::template::input_cache::Manifest;
+use crate::template::{Day, aoc_cli, terminal_markdown};
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+
+    // only hit the network when the puzzle has not been downloaded yet.
+    if !Path::new(&puzzle_path).exists() {
+        if aoc_cli::check().is_err() {
+            eprintln!(
+                "puzzle file \"{puzzle_path}\" not found and command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            );
+            process::exit(1);
+        }
+
+        // only fetch the puzzle, a cached input must not be overwritten by reading.
+        if let Err(e) = aoc_cli::download_puzzle(day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        };
+
+        let mut manifest = Manifest::read_from_file();
+        manifest.record_puzzle(day);
+        if let Err(e) = manifest.store_file() {
+            eprintln!("Failed to store checksum manifest: {e}");
+        }
+
+        println!();
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => {
+            let width = terminal_markdown::terminal_width();
+            println!("{}", terminal_markdown::render(&markdown, width));
+        }
+        Err(e) => {
+            eprintln!("failed to read puzzle file \"{puzzle_path}\": {e}");
+            process::exit(1);
+        }
+    }
 }
@@ -1,8 +1,27 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_multi::child_commands;
+use crate::template::{Day, watch};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    watch: bool,
+    test: bool,
+) {
+    if watch {
+        // profiling and submitting on every change makes no sense, so those flags are ignored.
+        watch::watch(day, || {
+            if test {
+                run_tests(day);
+            }
+            child_commands::run_solution(day, false, release).unwrap_or_default()
+        });
+        return;
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -32,3 +51,14 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+fn run_tests(day: Day) {
+    let mut cmd = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -1,10 +1,17 @@
 use std::collections::HashSet;
 
+use crate::template::report::{self, ReportOptions, ReportTarget};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
 use crate::template::{Day, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    target: &ReportTarget,
+    options: &ReportOptions,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -28,7 +35,11 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        if report::store_runtime_chart(&merged_timings).is_err() {
+            eprintln!("Failed to store runtime chart.");
+        }
+
+        match readme_benchmarks::update(merged_timings, target, options) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -0,0 +1,281 @@
+/// Module that keeps track of downloaded inputs and puzzles.
+/// Checksums of every download are stored in a manifest, which allows to skip redundant downloads
+/// and to notice when an input changed after it was downloaded.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::{fs, io, process, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, aoc_cli};
+
+static MANIFEST_FILE_PATH: &str = "./data/checksums.json";
+
+/// Responses aoc serves in place of an input when a request was not successful.
+const ERROR_RESPONSES: [&str; 3] = [
+    "Puzzle inputs differ by user",
+    "Please log in",
+    "Please don't repeatedly request this endpoint before it unlocks",
+];
+
+/// Problems that make an input file unusable for a solution.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputError {
+    Missing,
+    Empty,
+    /// The file contains an HTML page or an error message instead of an input.
+    ErrorResponse,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing => write!(f, "input file does not exist."),
+            InputError::Empty => write!(f, "input file is empty."),
+            InputError::ErrorResponse => write!(
+                f,
+                "input file contains an error response instead of an input, is your session cookie valid?"
+            ),
+        }
+    }
+}
+
+/// Checks whether `input` looks like an actual puzzle input.
+pub fn validate_input(input: &str) -> Result<(), InputError> {
+    let trimmed = input.trim_start();
+
+    if trimmed.is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    let head = trimmed
+        .get(..trimmed.len().min(200))
+        .unwrap_or(trimmed)
+        .to_ascii_lowercase();
+
+    let is_html = head.starts_with("<!doctype html") || head.starts_with("<html");
+    let is_error_message = ERROR_RESPONSES.iter().any(|msg| trimmed.starts_with(msg));
+
+    if is_html || is_error_message {
+        return Err(InputError::ErrorResponse);
+    }
+
+    Ok(())
+}
+
+/// Reads and validates the input file of a day.
+pub fn read_valid_input(day: Day) -> Result<String, InputError> {
+    let input =
+        fs::read_to_string(aoc_cli::get_input_path(day)).map_err(|_| InputError::Missing)?;
+    validate_input(&input)?;
+    Ok(input)
+}
+
+/// 64-bit FNV-1a hash, hex encoded. Stable across platforms and Rust versions.
+pub fn checksum(bytes: &[u8]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    });
+
+    format!("{hash:016x}")
+}
+
+/// Checksums of downloaded inputs and puzzles.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub inputs: HashMap<Day, String>,
+    pub puzzles: HashMap<Day, String>,
+}
+
+impl Manifest {
+    /// Dehydrate the manifest to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(MANIFEST_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the manifest from a JSON file. If not present, returns an empty manifest.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(MANIFEST_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Manifest::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Records the checksums of the files currently on disk for a day. Invalid inputs are skipped.
+    pub fn record(&mut self, day: Day) {
+        if let Ok(input) = read_valid_input(day) {
+            self.inputs.insert(day, checksum(input.as_bytes()));
+        }
+
+        self.record_puzzle(day);
+    }
+
+    /// Records the checksum of the puzzle currently on disk for a day, leaving the input as is.
+    pub fn record_puzzle(&mut self, day: Day) {
+        if let Ok(puzzle) = fs::read(aoc_cli::get_puzzle_path(day)) {
+            self.puzzles.insert(day, checksum(&puzzle));
+        }
+    }
+
+    /// Whether `input` differs from the input recorded for a day.
+    /// Inputs that have not been recorded yet are not considered changed.
+    pub fn is_input_changed(&self, day: Day, input: &str) -> bool {
+        self.inputs
+            .get(&day)
+            .is_some_and(|recorded| *recorded != checksum(input.as_bytes()))
+    }
+}
+
+/// Runs before a solution: exits if the input is unusable and warns if it changed since download.
+pub fn check_input(day: Day, input: &str) {
+    if let Err(e) = validate_input(input) {
+        eprintln!("{ANSI_BOLD}Invalid input for day {day}:{ANSI_RESET} {e}");
+        eprintln!("Try re-downloading it with `cargo download {day} --force`.");
+        process::exit(1);
+    }
+
+    if Manifest::read_from_file().is_input_changed(day, input) {
+        eprintln!(
+            "{ANSI_BOLD}Warning:{ANSI_RESET} input for day {day} changed since it was downloaded."
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn checksums_to_json(checksums: &HashMap<Day, String>) -> JsonValue {
+    JsonValue::Object(
+        checksums
+            .iter()
+            .map(|(day, sum)| (day.to_string(), JsonValue::String(sum.clone())))
+            .collect(),
+    )
+}
+
+fn checksums_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<HashMap<Day, String>, String> {
+    let Some(value) = json.get(key) else {
+        return Ok(HashMap::new());
+    };
+
+    value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or(format!("expected `json.{key}` to be an object."))?
+        .iter()
+        .map(|(day, sum)| {
+            let day = Day::from_str(day).map_err(|e| e.to_string())?;
+            let sum = sum
+                .get::<String>()
+                .ok_or(format!("expected `json.{key}.{day}` to be a string."))?;
+            Ok((day, sum.clone()))
+        })
+        .collect()
+}
+
+impl From<Manifest> for JsonValue {
+    fn from(value: Manifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("inputs".into(), checksums_to_json(&value.inputs));
+        map.insert("puzzles".into(), checksums_to_json(&value.puzzles));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let map = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(Manifest {
+            inputs: checksums_from_json(map, "inputs")?,
+            puzzles: checksums_from_json(map, "puzzles")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use tinyjson::JsonValue;
+
+    use super::{InputError, Manifest, checksum, validate_input};
+    use crate::day;
+
+    #[test]
+    fn accepts_inputs() {
+        assert_eq!(validate_input("()())\n"), Ok(()));
+        assert_eq!(validate_input("<<>>^^v"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_empty_inputs() {
+        assert_eq!(validate_input(""), Err(InputError::Empty));
+        assert_eq!(validate_input(" \n\n"), Err(InputError::Empty));
+    }
+
+    #[test]
+    fn rejects_error_responses() {
+        assert_eq!(
+            validate_input("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InputError::ErrorResponse)
+        );
+        assert_eq!(
+            validate_input(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(InputError::ErrorResponse)
+        );
+    }
+
+    #[test]
+    fn computes_stable_checksums() {
+        assert_eq!(checksum(b""), "cbf29ce484222325");
+        assert_eq!(checksum(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn detects_changed_inputs() {
+        let manifest = Manifest {
+            inputs: HashMap::from([(day!(1), checksum(b"()"))]),
+            puzzles: HashMap::new(),
+        };
+        assert!(!manifest.is_input_changed(day!(1), "()"));
+        assert!(manifest.is_input_changed(day!(1), "(("));
+        assert!(!manifest.is_input_changed(day!(2), "(("));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let manifest = Manifest {
+            inputs: HashMap::from([(day!(1), "abc".into()), (day!(2), "def".into())]),
+            puzzles: HashMap::from([(day!(1), "123".into())]),
+        };
+        let json = JsonValue::from(manifest.clone()).stringify().unwrap();
+        assert_eq!(Manifest::try_from(json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn handles_malformed_json() {
+        assert_eq!(
+            Manifest::try_from("{}".to_string()).unwrap(),
+            Manifest::default()
+        );
+        assert!(Manifest::try_from(r#"{ "inputs": { "26": "abc" } }"#.to_string()).is_err());
+    }
+}
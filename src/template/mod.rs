@@ -1,7 +1,10 @@
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod input_cache;
+pub mod report;
 pub mod runner;
 
 pub use day::*;
@@ -9,7 +12,9 @@ pub use day::*;
 mod day;
 mod readme_benchmarks;
 mod run_multi;
+mod terminal_markdown;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -59,9 +64,14 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(not(feature = "dhat-heap"))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
+            $crate::template::input_cache::check_input(DAY, &input);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
@@ -2,11 +2,9 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
+use crate::template::report::{self, ReportOptions, ReportTarget};
 use crate::template::timings::Timings;
 
-static MARKER: &str = "<!--- benchmarking table --->";
-
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,13 +23,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,54 +45,46 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
-    }
-
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
-
-    lines.join("\n")
-}
-
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    marker: &str,
+    timings: Timings,
+    total_millis: f64,
+    options: &ReportOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    let table = report::render(timings, total_millis, options);
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &[marker, &table, marker].join("\n"),
+    );
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+/// Writes a report of `timings` to `target`. Embeddable formats replace the section between the
+/// target's markers, all other formats replace the whole file.
+pub fn update(
+    timings: Timings,
+    target: &ReportTarget,
+    options: &ReportOptions,
+) -> Result<(), Error> {
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+
+    if !options.format.is_embeddable() {
+        fs::write(&target.path, report::render(timings, total_millis, options))?;
+        return Ok(());
+    }
+
+    let mut readme = String::from_utf8_lossy(&fs::read(&target.path)?).to_string();
+    update_content(&mut readme, &target.marker, timings, total_millis, options)?;
+    fs::write(&target.path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
+    use super::update_content;
+    use crate::template::report::{ChartStyle, DEFAULT_MARKER as MARKER, Format, ReportOptions};
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
@@ -110,18 +95,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    allocs_1: None,
+                    allocs_2: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    allocs_1: None,
+                    allocs_2: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    allocs_1: None,
+                    allocs_2: None,
                 },
             ],
         }
@@ -131,28 +122,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +186,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +213,34 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_content_with_custom_marker() {
+        let marker = "<!-- docs benchmarks -->";
+        let mut s = format!("foo\n{}{}\n{}", marker, marker, MARKER);
+        update_content(
+            &mut s,
+            marker,
+            get_mock_timings(),
+            190.0,
+            &ReportOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(s.matches(marker).collect::<Vec<&str>>().len(), 2);
+        assert!(s.ends_with(&format!("**Total: 190.00ms**\n{}\n{}", marker, MARKER)));
+    }
+
+    #[test]
+    fn embeds_html_with_chart() {
+        let mut s = format!("<body>\n{}\n{}\n</body>", MARKER, MARKER);
+        let options = ReportOptions {
+            format: Format::Html,
+            chart: Some(ChartStyle::Ascii),
+            ..Default::default()
+        };
+        update_content(&mut s, MARKER, get_mock_timings(), 190.0, &options).unwrap();
+        assert!(s.contains("<h2>Benchmarks</h2>"));
+        assert!(s.contains("<pre>\nDay 01 · Part 1 │"));
+        assert!(s.ends_with(&format!("</pre>\n{}\n</body>", MARKER)));
+    }
 }
@@ -0,0 +1,317 @@
+/// Per-part bar charts that can be appended to Markdown and HTML reports, as well as a
+/// self-contained SVG chart of all stored timings.
+use std::str::FromStr;
+use std::{fs, io};
+
+use super::{Report, ReportOptionFromStrError};
+use crate::template::timings::{Timing, Timings};
+
+static CHART_FILE_PATH: &str = "./data/timings.svg";
+
+const ASCII_BAR_WIDTH: usize = 40;
+
+const SVG_ROW_HEIGHT: usize = 20;
+const SVG_BAR_HEIGHT: usize = 14;
+const SVG_LABEL_WIDTH: usize = 130;
+const SVG_BAR_WIDTH: usize = 400;
+const SVG_VALUE_WIDTH: usize = 90;
+const SVG_TITLE_HEIGHT: usize = 30;
+const SVG_AXIS_HEIGHT: usize = 30;
+const SVG_PADDING: usize = 10;
+
+/// Bars are colored by the slowest threshold they exceed, in nanoseconds.
+const SVG_THRESHOLDS: [(f64, &str); 3] = [
+    (100_000_000.0, "#c0392b"),
+    (1_000_000.0, "#d4a017"),
+    (0.0, "#4c9a2a"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartStyle {
+    Ascii,
+    Svg,
+}
+
+impl FromStr for ChartStyle {
+    type Err = ReportOptionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(Self::Ascii),
+            "svg" => Ok(Self::Svg),
+            _ => Err(ReportOptionFromStrError::new(s, "`ascii` or `svg`")),
+        }
+    }
+}
+
+/// A single bar of a chart, i.e. one part of one day.
+struct Bar<'a> {
+    label: String,
+    duration: &'a str,
+    nanos: f64,
+}
+
+fn bars(timings: &[Timing]) -> Vec<Bar<'_>> {
+    timings
+        .iter()
+        .flat_map(|timing| {
+            (1..=2).filter_map(move |part| {
+                Some(Bar {
+                    label: format!("Day {} · Part {part}", timing.day),
+                    duration: timing.part(part)?,
+                    nanos: timing.part_nanos(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Length of a bar relative to the slowest part, at least 1 so that every part stays visible.
+fn bar_length(nanos: f64, max_nanos: f64, width: usize) -> usize {
+    if max_nanos <= 0.0 {
+        return 1;
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let length = ((nanos / max_nanos) * width as f64).round() as usize;
+    length.clamp(1, width)
+}
+
+/// Length of a bar on a log scale spanning the powers of ten `min_exp..=max_exp`.
+fn log_bar_length(nanos: f64, min_exp: i32, max_exp: i32, width: usize) -> usize {
+    let span = f64::from(max_exp - min_exp);
+    let position = (nanos.max(1.0).log10() - f64::from(min_exp)) / span;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let length = (position * width as f64).round() as usize;
+    length.clamp(1, width)
+}
+
+/// The powers of ten enclosing all bars, spanning at least one decade.
+fn log_bounds(bars: &[Bar]) -> (i32, i32) {
+    if bars.is_empty() {
+        return (0, 1);
+    }
+
+    let min_nanos = bars.iter().map(|b| b.nanos).fold(f64::INFINITY, f64::min);
+    let max_nanos = max_nanos(bars);
+
+    #[allow(clippy::cast_possible_truncation)]
+    let min_exp = min_nanos.max(1.0).log10().floor() as i32;
+    #[allow(clippy::cast_possible_truncation)]
+    let max_exp = max_nanos.max(1.0).log10().ceil() as i32;
+
+    (min_exp, max_exp.max(min_exp + 1))
+}
+
+fn max_nanos(bars: &[Bar]) -> f64 {
+    bars.iter().map(|b| b.nanos).fold(0.0, f64::max)
+}
+
+fn bar_color(nanos: f64) -> &'static str {
+    SVG_THRESHOLDS
+        .iter()
+        .find(|(threshold, _)| nanos >= *threshold)
+        .map_or(SVG_THRESHOLDS[SVG_THRESHOLDS.len() - 1].1, |(_, color)| {
+            color
+        })
+}
+
+/// Formats a power of ten of nanoseconds as an axis label, e.g. `10µs`.
+fn format_decade(exp: i32) -> String {
+    let units = ["ns", "µs", "ms", "s"];
+    let unit_idx = usize::try_from(exp / 3).unwrap_or(0).min(units.len() - 1);
+    let value = 10_u64.pow(u32::try_from(exp).unwrap_or(0) - 3 * unit_idx as u32);
+    format!("{value}{}", units[unit_idx])
+}
+
+fn svg_bar(lines: &mut Vec<String>, bar: &Bar, y: usize, length: usize) {
+    let text_y = y + SVG_ROW_HEIGHT / 2 + 4;
+    let bar_y = y + (SVG_ROW_HEIGHT - SVG_BAR_HEIGHT) / 2;
+
+    lines.push(format!(
+        r#"  <text x="{SVG_PADDING}" y="{text_y}">{}</text>"#,
+        bar.label
+    ));
+    lines.push(format!(
+        r#"  <rect x="{}" y="{bar_y}" width="{length}" height="{SVG_BAR_HEIGHT}" fill="{}"/>"#,
+        SVG_PADDING + SVG_LABEL_WIDTH,
+        bar_color(bar.nanos)
+    ));
+    lines.push(format!(
+        r#"  <text x="{}" y="{text_y}">{}</text>"#,
+        SVG_PADDING + SVG_LABEL_WIDTH + length + 6,
+        bar.duration
+    ));
+}
+
+pub fn ascii(report: &Report) -> String {
+    let bars = bars(&report.timings);
+    let max_nanos = max_nanos(&bars);
+
+    bars.iter()
+        .map(|bar| {
+            let length = bar_length(bar.nanos, max_nanos, ASCII_BAR_WIDTH);
+            format!(
+                "{} │{:<ASCII_BAR_WIDTH$}│ {}",
+                bar.label,
+                "█".repeat(length),
+                bar.duration
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn svg(report: &Report) -> String {
+    let bars = bars(&report.timings);
+    let max_nanos = max_nanos(&bars);
+
+    let width = 2 * SVG_PADDING + SVG_LABEL_WIDTH + SVG_BAR_WIDTH + SVG_VALUE_WIDTH;
+    let height = bars.len() * SVG_ROW_HEIGHT;
+
+    let mut lines = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="12">"#
+    )];
+
+    for (i, bar) in bars.iter().enumerate() {
+        let length = bar_length(bar.nanos, max_nanos, SVG_BAR_WIDTH);
+        svg_bar(&mut lines, bar, i * SVG_ROW_HEIGHT, length);
+    }
+
+    lines.push("</svg>".into());
+    lines.join("\n")
+}
+
+/// Renders a self-contained SVG document with one bar per day and part on a log scale.
+pub fn runtime_svg(timings: &Timings) -> String {
+    let bars = bars(&timings.data);
+    let (min_exp, max_exp) = log_bounds(&bars);
+
+    let chart_top = SVG_TITLE_HEIGHT;
+    let chart_bottom = chart_top + bars.len() * SVG_ROW_HEIGHT;
+    let bar_left = SVG_PADDING + SVG_LABEL_WIDTH;
+
+    let width = 2 * SVG_PADDING + SVG_LABEL_WIDTH + SVG_BAR_WIDTH + SVG_VALUE_WIDTH;
+    let height = chart_bottom + SVG_AXIS_HEIGHT;
+
+    let mut lines = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="12">"#
+        ),
+        r#"  <rect width="100%" height="100%" fill="white"/>"#.into(),
+        format!(
+            r#"  <text x="{SVG_PADDING}" y="20" font-size="14" font-weight="bold">Runtime per part (total: {:.2}ms)</text>"#,
+            timings.total_millis()
+        ),
+    ];
+
+    for exp in min_exp..=max_exp {
+        let x = bar_left + log_bar_length(10_f64.powi(exp), min_exp, max_exp, SVG_BAR_WIDTH);
+        // the lowest decade sits on the axis itself.
+        let x = if exp == min_exp { bar_left } else { x };
+
+        lines.push(format!(
+            r##"  <line x1="{x}" y1="{chart_top}" x2="{x}" y2="{chart_bottom}" stroke="#ddd"/>"##
+        ));
+        lines.push(format!(
+            r#"  <text x="{x}" y="{}" text-anchor="middle" fill="gray">{}</text>"#,
+            chart_bottom + 16,
+            format_decade(exp)
+        ));
+    }
+
+    for (i, bar) in bars.iter().enumerate() {
+        let length = log_bar_length(bar.nanos, min_exp, max_exp, SVG_BAR_WIDTH);
+        svg_bar(&mut lines, bar, chart_top + i * SVG_ROW_HEIGHT, length);
+    }
+
+    lines.push("</svg>".into());
+    lines.join("\n")
+}
+
+/// Write the runtime chart of `timings` next to the stored timings.
+pub fn store_runtime_chart(timings: &Timings) -> Result<(), io::Error> {
+    fs::write(CHART_FILE_PATH, runtime_svg(timings) + "\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ascii, bar_color, bar_length, format_decade, log_bar_length, runtime_svg, svg};
+    use crate::template::report::{Report, ReportOptions, tests::get_mock_timings};
+
+    #[test]
+    fn scales_bars_to_slowest_part() {
+        assert_eq!(bar_length(50.0, 50.0, 40), 40);
+        assert_eq!(bar_length(25.0, 50.0, 40), 20);
+        assert_eq!(bar_length(0.0, 50.0, 40), 1);
+        assert_eq!(bar_length(0.0, 0.0, 40), 1);
+    }
+
+    #[test]
+    fn renders_ascii_chart() {
+        let report = Report::new(get_mock_timings(), 150.0, &ReportOptions::default());
+        let chart = ascii(&report);
+        let lines: Vec<&str> = chart.lines().collect();
+
+        // day 2 has no second part.
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0],
+            format!("Day 01 · Part 1 │{:<40}│ 10.0ms", "█".repeat(8))
+        );
+        assert_eq!(
+            lines[4],
+            format!("Day 04 · Part 2 │{}│ 50.0ms", "█".repeat(40))
+        );
+    }
+
+    #[test]
+    fn renders_svg_chart() {
+        let report = Report::new(get_mock_timings(), 150.0, &ReportOptions::default());
+        let chart = svg(&report);
+        assert!(chart.starts_with("<svg"));
+        assert!(chart.ends_with("</svg>"));
+        assert_eq!(chart.matches("<rect").count(), 5);
+        assert!(chart.contains(r#"width="400""#));
+    }
+
+    #[test]
+    fn scales_bars_logarithmically() {
+        assert_eq!(log_bar_length(1_000.0, 3, 7, 400), 1);
+        assert_eq!(log_bar_length(100_000.0, 3, 7, 400), 200);
+        assert_eq!(log_bar_length(10_000_000.0, 3, 7, 400), 400);
+    }
+
+    #[test]
+    fn colors_bars_by_threshold() {
+        assert_eq!(bar_color(500.0), "#4c9a2a");
+        assert_eq!(bar_color(5_000_000.0), "#d4a017");
+        assert_eq!(bar_color(500_000_000.0), "#c0392b");
+    }
+
+    #[test]
+    fn formats_axis_labels() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(8), "100ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn renders_runtime_chart() {
+        let chart = runtime_svg(&get_mock_timings());
+        assert!(chart.starts_with("<svg"));
+        assert!(chart.contains("total: 150.00ms"));
+        // 10ms to 50ms fit into the decades 10ms..100ms.
+        assert!(chart.contains(">10ms</text>"));
+        assert!(chart.contains(">100ms</text>"));
+        assert_eq!(chart.matches(r##"fill="#d4a017""##).count(), 5);
+    }
+
+    #[test]
+    fn renders_empty_runtime_chart() {
+        let chart = runtime_svg(&Default::default());
+        assert_eq!(chart.matches("<rect").count(), 1);
+    }
+}
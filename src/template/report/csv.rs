@@ -0,0 +1,63 @@
+use super::{Renderer, Report};
+use crate::template::alloc::AllocStats;
+
+/// Renders one row per day, with durations both formatted and in nanoseconds,
+/// and the number and bytes of allocations of each part.
+pub struct CsvRenderer;
+
+const HEADER: &str = "day,part_1,part_2,part_1_nanos,part_2_nanos,total_nanos,\
+    part_1_allocs,part_1_alloc_bytes,part_2_allocs,part_2_alloc_bytes";
+
+fn nanos_cell(nanos: Option<f64>) -> String {
+    nanos.map(|n| n.to_string()).unwrap_or_default()
+}
+
+fn allocs_cells(allocs: Option<AllocStats>) -> String {
+    allocs
+        .map(|a| format!("{},{}", a.count, a.bytes))
+        .unwrap_or_else(|| ",".into())
+}
+
+impl Renderer for CsvRenderer {
+    fn render(&self, report: &Report) -> String {
+        let mut lines: Vec<String> = vec![HEADER.into()];
+
+        for timing in &report.timings {
+            lines.push(format!(
+                "{},{},{},{},{},{},{},{}",
+                timing.day,
+                timing.part(1).unwrap_or_default(),
+                timing.part(2).unwrap_or_default(),
+                nanos_cell(timing.part_nanos(1)),
+                nanos_cell(timing.part_nanos(2)),
+                timing.total_nanos,
+                allocs_cells(timing.allocs(1)),
+                allocs_cells(timing.allocs(2))
+            ));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::CsvRenderer;
+    use crate::template::report::{Renderer, Report, ReportOptions, tests::get_mock_timings};
+
+    #[test]
+    fn renders_csv_rows() {
+        let report = Report::new(get_mock_timings(), 150.0, &ReportOptions::default());
+        let expected = [
+            "day,part_1,part_2,part_1_nanos,part_2_nanos,total_nanos,\
+             part_1_allocs,part_1_alloc_bytes,part_2_allocs,part_2_alloc_bytes",
+            "01,10.0ms,20.0ms,10000000,20000000,30000000,3,276,1,1024",
+            "02,30.0ms,,30000000,,30000000,,,,",
+            "04,40.0ms,50.0ms,40000000,50000000,90000000,,,,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(CsvRenderer.render(&report), expected);
+    }
+}
@@ -0,0 +1,78 @@
+use super::{ChartStyle, Renderer, Report, chart, get_path_for_bin};
+
+/// Renders an HTML table, e.g. to embed the benchmarks into a docs page.
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, report: &Report) -> String {
+        let has_allocs = report.has_allocs();
+        let header = if has_allocs {
+            "    <tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Allocations</th></tr>"
+        } else {
+            "    <tr><th>Day</th><th>Part 1</th><th>Part 2</th></tr>"
+        };
+
+        let mut lines: Vec<String> = vec![
+            "<h2>Benchmarks</h2>".into(),
+            "<table>".into(),
+            "  <thead>".into(),
+            header.into(),
+            "  </thead>".into(),
+            "  <tbody>".into(),
+        ];
+
+        for timing in &report.timings {
+            let path = get_path_for_bin(timing.day);
+            let allocs = match (has_allocs, timing.total_allocs()) {
+                (false, _) => String::new(),
+                (true, Some(allocs)) => format!("<td><code>{allocs}</code></td>"),
+                (true, None) => "<td>-</td>".into(),
+            };
+            lines.push(format!(
+                r#"    <tr><td><a href="{}">Day {}</a></td><td><code>{}</code></td><td><code>{}</code></td>{}</tr>"#,
+                path,
+                timing.day.into_inner(),
+                timing.part(1).unwrap_or("-"),
+                timing.part(2).unwrap_or("-"),
+                allocs
+            ));
+        }
+
+        lines.push("  </tbody>".into());
+        lines.push("</table>".into());
+        lines.push(format!(
+            "<p><strong>Total: {:.2}ms</strong></p>",
+            report.total_millis
+        ));
+
+        match report.chart {
+            Some(ChartStyle::Ascii) => {
+                lines.push("<pre>".into());
+                lines.push(chart::ascii(report));
+                lines.push("</pre>".into());
+            }
+            Some(ChartStyle::Svg) => lines.push(chart::svg(report)),
+            None => {}
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::HtmlRenderer;
+    use crate::template::report::{Renderer, Report, ReportOptions, tests::get_mock_timings};
+
+    #[test]
+    fn renders_html_table() {
+        let report = Report::new(get_mock_timings(), 150.0, &ReportOptions::default());
+        let html = HtmlRenderer.render(&report);
+        assert!(html.contains(
+            r#"<tr><td><a href="./src/bin/02.rs">Day 2</a></td><td><code>30.0ms</code></td><td><code>-</code></td><td>-</td></tr>"#
+        ));
+        assert!(html.contains("<th>Allocations</th>"));
+        assert!(html.contains("<td><code>4 allocs (1.3 KiB)</code></td>"));
+        assert!(html.ends_with("<p><strong>Total: 150.00ms</strong></p>"));
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use tinyjson::JsonValue;
+
+use super::{Renderer, Report};
+
+/// Renders the timings in the format of `data/timings.json`, including allocations,
+/// extended with per-part nanoseconds.
+pub struct JsonRenderer;
+
+fn nanos_value(nanos: Option<f64>) -> JsonValue {
+    nanos.map_or(JsonValue::Null, JsonValue::Number)
+}
+
+impl Renderer for JsonRenderer {
+    fn render(&self, report: &Report) -> String {
+        let data = report
+            .timings
+            .iter()
+            .map(|timing| {
+                let mut value = JsonValue::from(timing);
+                if let JsonValue::Object(map) = &mut value {
+                    map.insert("part_1_nanos".into(), nanos_value(timing.part_nanos(1)));
+                    map.insert("part_2_nanos".into(), nanos_value(timing.part_nanos(2)));
+                }
+                value
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("data".into(), JsonValue::Array(data));
+        map.insert(
+            "total_millis".into(),
+            JsonValue::Number(report.total_millis),
+        );
+
+        // formatting only fails on non-finite numbers, which durations never are.
+        JsonValue::Object(map).format().unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use tinyjson::JsonValue;
+
+    use super::JsonRenderer;
+    use crate::template::report::{Renderer, Report, ReportOptions, tests::get_mock_timings};
+
+    #[test]
+    fn renders_parseable_json() {
+        let report = Report::new(get_mock_timings(), 150.0, &ReportOptions::default());
+        let json: JsonValue = JsonRenderer.render(&report).parse().unwrap();
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["total_millis"].get::<f64>(), Some(&150.0));
+
+        let data = map["data"].get::<Vec<JsonValue>>().unwrap();
+        assert_eq!(data.len(), 3);
+
+        let day_2 = data[1].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(day_2["part_1_nanos"].get::<f64>(), Some(&30_000_000.0));
+        assert!(day_2["part_2_nanos"].is_null());
+        assert!(day_2["part_1_allocs"].is_null());
+
+        let day_1 = data[0].get::<HashMap<String, JsonValue>>().unwrap();
+        let allocs = day_1["part_1_allocs"]
+            .get::<HashMap<String, JsonValue>>()
+            .unwrap();
+        assert_eq!(allocs["count"].get::<f64>(), Some(&3.0));
+        assert_eq!(allocs["bytes"].get::<f64>(), Some(&276.0));
+    }
+}
@@ -0,0 +1,81 @@
+use super::{ChartStyle, Renderer, Report, chart, get_path_for_bin};
+
+/// Renders a Markdown table, the format used by the readme.
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, report: &Report) -> String {
+        let has_allocs = report.has_allocs();
+        let (header, separator) = if has_allocs {
+            (
+                "| Day | Part 1 | Part 2 | Allocations |",
+                "| :---: | :---: | :---:  | :---: |",
+            )
+        } else {
+            ("| Day | Part 1 | Part 2 |", "| :---: | :---: | :---:  |")
+        };
+
+        let mut lines: Vec<String> = vec![
+            "## Benchmarks".into(),
+            String::new(),
+            header.into(),
+            separator.into(),
+        ];
+
+        for timing in &report.timings {
+            let path = get_path_for_bin(timing.day);
+            let mut row = format!(
+                "| [Day {}]({}) | `{}` | `{}` |",
+                timing.day.into_inner(),
+                path,
+                timing.part(1).unwrap_or("-"),
+                timing.part(2).unwrap_or("-")
+            );
+            if has_allocs {
+                match timing.total_allocs() {
+                    Some(allocs) => row.push_str(&format!(" `{allocs}` |")),
+                    None => row.push_str(" - |"),
+                }
+            }
+            lines.push(row);
+        }
+
+        lines.push(String::new());
+        lines.push(format!("**Total: {:.2}ms**", report.total_millis));
+
+        match report.chart {
+            Some(ChartStyle::Ascii) => {
+                lines.push(String::new());
+                lines.push("```text".into());
+                lines.push(chart::ascii(report));
+                lines.push("```".into());
+            }
+            Some(ChartStyle::Svg) => {
+                lines.push(String::new());
+                lines.push(chart::svg(report));
+            }
+            None => {}
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::MarkdownRenderer;
+    use crate::template::report::{Renderer, Report, ReportOptions, tests::get_mock_timings};
+
+    #[test]
+    fn renders_allocations_column() {
+        let report = Report::new(get_mock_timings(), 150.0, &ReportOptions::default());
+        let markdown = MarkdownRenderer.render(&report);
+        assert!(markdown.contains("| Day | Part 1 | Part 2 | Allocations |"));
+        assert!(
+            markdown.contains(
+                "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `4 allocs (1.3 KiB)` |"
+            )
+        );
+        assert!(markdown.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `-` | - |"));
+    }
+}
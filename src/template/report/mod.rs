@@ -0,0 +1,268 @@
+/// Module that renders benchmark timings into reports of different formats.
+/// Markdown and HTML reports can be embedded into an existing document between two markers,
+/// CSV and JSON reports always replace the contents of their target file.
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::template::Day;
+use crate::template::timings::{Timing, Timings};
+
+pub use chart::{ChartStyle, runtime_svg, store_runtime_chart};
+
+mod chart;
+mod csv;
+mod html;
+mod json;
+mod markdown;
+
+pub const DEFAULT_TARGET: &str = "README.md";
+pub const DEFAULT_MARKER: &str = "<!--- benchmarking table --->";
+
+/// Output format of a benchmark report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Html,
+    Csv,
+    Json,
+}
+
+impl Format {
+    /// Guesses the format from the extension of a file path.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path).extension()?.to_str()?;
+        match extension.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Whether reports of this format can be embedded between markers in a larger document.
+    pub fn is_embeddable(self) -> bool {
+        matches!(self, Self::Markdown | Self::Html)
+    }
+
+    pub fn renderer(self) -> Box<dyn Renderer> {
+        match self {
+            Self::Markdown => Box::new(markdown::MarkdownRenderer),
+            Self::Html => Box::new(html::HtmlRenderer),
+            Self::Csv => Box::new(csv::CsvRenderer),
+            Self::Json => Box::new(json::JsonRenderer),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = ReportOptionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(ReportOptionFromStrError::new(
+                s,
+                "`markdown`, `html`, `csv` or `json`",
+            )),
+        }
+    }
+}
+
+/// Order of the rows of a benchmark report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest days first.
+    Runtime,
+}
+
+impl FromStr for SortOrder {
+    type Err = ReportOptionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "runtime" => Ok(Self::Runtime),
+            _ => Err(ReportOptionFromStrError::new(s, "`day` or `runtime`")),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a report option.
+#[derive(Debug)]
+pub struct ReportOptionFromStrError {
+    value: String,
+    expected: &'static str,
+}
+
+impl ReportOptionFromStrError {
+    fn new(value: &str, expected: &'static str) -> Self {
+        Self {
+            value: value.into(),
+            expected,
+        }
+    }
+}
+
+impl Error for ReportOptionFromStrError {}
+
+impl Display for ReportOptionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown value `{}`, expecting {}",
+            self.value, self.expected
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    pub format: Format,
+    pub sort: SortOrder,
+    pub chart: Option<ChartStyle>,
+}
+
+/// File a report is written to. For embeddable formats, the report replaces the content between
+/// the first and the last occurrence of `marker`.
+#[derive(Debug, Clone)]
+pub struct ReportTarget {
+    pub path: String,
+    pub marker: String,
+}
+
+impl Default for ReportTarget {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_TARGET.into(),
+            marker: DEFAULT_MARKER.into(),
+        }
+    }
+}
+
+/// Timings prepared for rendering, i.e. sorted according to the report options.
+pub struct Report {
+    pub timings: Vec<Timing>,
+    pub total_millis: f64,
+    pub chart: Option<ChartStyle>,
+}
+
+impl Report {
+    pub fn new(timings: Timings, total_millis: f64, options: &ReportOptions) -> Self {
+        let mut timings = timings.data;
+
+        if options.sort == SortOrder::Runtime {
+            timings.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+        }
+
+        Report {
+            timings,
+            total_millis,
+            chart: options.chart,
+        }
+    }
+
+    /// Whether allocations were counted for any day, otherwise tables leave out their column.
+    pub fn has_allocs(&self) -> bool {
+        self.timings.iter().any(|t| t.total_allocs().is_some())
+    }
+}
+
+/// Turns a [`Report`] into the contents of a report file.
+pub trait Renderer {
+    fn render(&self, report: &Report) -> String;
+}
+
+/// Render timings with the renderer for the configured format.
+pub fn render(timings: Timings, total_millis: f64, options: &ReportOptions) -> String {
+    let report = Report::new(timings, total_millis, options);
+    options.format.renderer().render(&report)
+}
+
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    format!("./src/bin/{day}.rs")
+}
+
+#[cfg(feature = "test_lib")]
+pub(crate) mod tests {
+    use std::str::FromStr;
+
+    use super::{Format, Report, ReportOptions, SortOrder};
+    use crate::template::alloc::AllocStats;
+    use crate::{day, template::timings::Timing, template::timings::Timings};
+
+    pub fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10.0ms".into()),
+                    part_2: Some("20.0ms".into()),
+                    total_nanos: 3e+7,
+                    allocs_1: Some(AllocStats {
+                        count: 3,
+                        bytes: 276,
+                    }),
+                    allocs_2: Some(AllocStats {
+                        count: 1,
+                        bytes: 1024,
+                    }),
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("30.0ms".into()),
+                    part_2: None,
+                    total_nanos: 3e+7,
+                    allocs_1: None,
+                    allocs_2: None,
+                },
+                Timing {
+                    day: day!(4),
+                    part_1: Some("40.0ms".into()),
+                    part_2: Some("50.0ms".into()),
+                    total_nanos: 9e+7,
+                    allocs_1: None,
+                    allocs_2: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn guesses_format_from_path() {
+        assert_eq!(Format::from_path("README.md"), Some(Format::Markdown));
+        assert_eq!(Format::from_path("docs/bench.HTML"), Some(Format::Html));
+        assert_eq!(Format::from_path("data/bench.csv"), Some(Format::Csv));
+        assert_eq!(Format::from_path("data/bench.json"), Some(Format::Json));
+        assert_eq!(Format::from_path("bench"), None);
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(Format::from_str("html").unwrap(), Format::Html);
+        assert_eq!(SortOrder::from_str("runtime").unwrap(), SortOrder::Runtime);
+        assert!(Format::from_str("xml").is_err());
+    }
+
+    #[test]
+    fn sorts_by_runtime() {
+        let options = ReportOptions {
+            sort: SortOrder::Runtime,
+            ..Default::default()
+        };
+        let report = Report::new(get_mock_timings(), 150.0, &options);
+        let days: Vec<u8> = report.timings.iter().map(|t| t.day.into_inner()).collect();
+        assert_eq!(days, vec![4, 1, 2]);
+    }
+}
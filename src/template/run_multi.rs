@@ -68,7 +68,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{Day, alloc::AllocStats, timings::parse_duration_nanos};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -134,6 +134,8 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            allocs_1: None,
+            allocs_2: None,
         };
 
         output
@@ -149,13 +151,15 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_allocs(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, allocs)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.allocs_1 = allocs;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.allocs_2 = allocs;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,12 +168,7 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,16 +178,17 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_duration_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
 
+    /// Parse the allocations printed after the timing, e.g. `(1.2ms @ 10 samples) [3 allocs, 276 bytes]`.
+    fn parse_allocs(line: &str) -> Option<AllocStats> {
+        let stats = line.rsplit_once(" samples) [")?.1.strip_suffix(']')?;
+        AllocStats::from_output(stats)
+    }
+
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -208,6 +208,7 @@ pub mod child_commands {
         use super::parse_exec_time;
 
         use crate::day;
+        use crate::template::alloc::AllocStats;
 
         #[test]
         fn parses_execution_times() {
@@ -222,6 +223,28 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.allocs_1, None);
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 276 bytes]".into(),
+                    "Part 2: [1] (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(
+                res.allocs_1,
+                Some(AllocStats {
+                    count: 3,
+                    bytes: 276
+                })
+            );
+            assert_eq!(res.allocs_2, None);
         }
 
         #[test]
@@ -7,15 +7,20 @@ use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
+use crate::template::alloc::{self, AllocStats};
 use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, samples, allocs) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut stats = format_duration(&duration, samples);
+    if let Some(allocs) = allocs {
+        stats.push_str(&format!(" [{}]", allocs.to_output()));
+    }
+    print_result(&result, &part_str, &stats);
 
     if let Some(result) = result {
         submit_result(result, day, part);
@@ -25,17 +30,19 @@ pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, da
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Allocations are counted for the first execution only.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocs) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
@@ -47,7 +54,7 @@ fn run_timed<I: Copy, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, allocs)
 }
 
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
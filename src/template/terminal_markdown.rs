@@ -0,0 +1,409 @@
+/// Module that renders puzzle descriptions (as written by aoc-cli) to the terminal.
+/// Only the subset of Markdown used by the puzzle descriptions is supported: headings, paragraphs,
+/// lists, code blocks, rules and the inline styles emphasis, strong emphasis, code and links.
+use std::env;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_CODE: &str = "\x1b[36m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+
+const DEFAULT_WIDTH: usize = 80;
+const MIN_WIDTH: usize = 20;
+const CODE_INDENT: &str = "    ";
+
+/// Width of the terminal. `$COLUMNS` takes precedence, as shells usually do not export it,
+/// the size of the attached terminal is queried otherwise, falling back to 80 columns.
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(tty_width)
+        .unwrap_or(DEFAULT_WIDTH)
+        .max(MIN_WIDTH)
+}
+
+/// Columns of the terminal attached to stdout, stderr or stdin, in that order.
+#[cfg(unix)]
+fn tty_width() -> Option<usize> {
+    [libc::STDOUT_FILENO, libc::STDERR_FILENO, libc::STDIN_FILENO]
+        .into_iter()
+        .find_map(|fd| {
+            let mut size = libc::winsize {
+                ws_row: 0,
+                ws_col: 0,
+                ws_xpixel: 0,
+                ws_ypixel: 0,
+            };
+            // SAFETY: `TIOCGWINSZ` only writes a `winsize` to the passed pointer.
+            let result = unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) };
+            (result == 0 && size.ws_col > 0).then_some(usize::from(size.ws_col))
+        })
+}
+
+#[cfg(not(unix))]
+fn tty_width() -> Option<usize> {
+    None
+}
+
+/// Removes ANSI escape sequences from a string.
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_escape = false;
+
+    for c in s.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Number of columns a string occupies, ignoring ANSI escape sequences.
+fn visible_width(s: &str) -> usize {
+    strip_ansi(s).chars().count()
+}
+
+/// Word-wraps styled text. Words longer than a line are not broken up.
+fn wrap(text: &str, width: usize, first_prefix: &str, rest_prefix: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_prefix.to_string();
+    let mut line_is_empty = true;
+
+    for word in text.split_whitespace() {
+        let word_width = visible_width(word);
+
+        if !line_is_empty && visible_width(&line) + 1 + word_width > width {
+            lines.push(line);
+            line = rest_prefix.to_string();
+            line_is_empty = true;
+        }
+
+        if !line_is_empty {
+            line.push(' ');
+        }
+
+        line.push_str(word);
+        line_is_empty = false;
+    }
+
+    if !line_is_empty {
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn active_styles(italic: bool, bold: bool) -> String {
+    let mut styles = String::new();
+    if italic {
+        styles.push_str(ANSI_ITALIC);
+    }
+    if bold {
+        styles.push_str(ANSI_BOLD);
+    }
+    styles
+}
+
+/// Converts inline Markdown into ANSI-styled text.
+fn render_inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut italic = false;
+    let mut bold = false;
+    let mut i = 0;
+
+    let find = |from: usize, needle: char| chars[from..].iter().position(|c| *c == needle);
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '\\' if next.is_some_and(|n| n.is_ascii_punctuation()) => {
+                out.push(next.unwrap());
+                i += 2;
+            }
+            '`' if let Some(len) = find(i + 1, '`') => {
+                let code: String = chars[i + 1..i + 1 + len].iter().collect();
+                out.push_str(ANSI_CODE);
+                out.push_str(&code);
+                out.push_str(ANSI_RESET);
+                out.push_str(&active_styles(italic, bold));
+                i += len + 2;
+            }
+            '*' if next == Some('*') => {
+                bold = !bold;
+                out.push_str(ANSI_RESET);
+                out.push_str(&active_styles(italic, bold));
+                i += 2;
+            }
+            // a lone asterisk followed by whitespace is not an emphasis marker, e.g. `2 * 3`.
+            '*' if italic || next.is_some_and(|n| !n.is_whitespace()) => {
+                italic = !italic;
+                out.push_str(ANSI_RESET);
+                out.push_str(&active_styles(italic, bold));
+                i += 1;
+            }
+            '[' if let Some(text_len) = find(i + 1, ']')
+                && chars.get(i + text_len + 2) == Some(&'(')
+                && let Some(url_len) = find(i + text_len + 3, ')') =>
+            {
+                let link_text: String = chars[i + 1..i + 1 + text_len].iter().collect();
+                out.push_str(ANSI_UNDERLINE);
+                out.push_str(&render_inline(&link_text));
+                out.push_str(ANSI_RESET);
+                out.push_str(&active_styles(italic, bold));
+                i += text_len + url_len + 4;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if italic || bold {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3
+        && (line.chars().all(|c| c == '-')
+            || line.chars().all(|c| c == '=')
+            || line.chars().all(|c| c == '*'))
+}
+
+/// Returns the content of a list item and its bullet, e.g. `("•", "text")` for `* text`.
+fn list_item(line: &str) -> Option<(String, &str)> {
+    let trimmed = line.trim_start();
+
+    if let Some(rest) = trimmed
+        .strip_prefix("* ")
+        .or_else(|| trimmed.strip_prefix("- "))
+        .or_else(|| trimmed.strip_prefix("+ "))
+    {
+        return Some(("•".into(), rest));
+    }
+
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = trimmed[digits..].strip_prefix(". ")?;
+        return Some((format!("{}.", &trimmed[..digits]), rest));
+    }
+
+    None
+}
+
+struct TerminalRenderer {
+    width: usize,
+    lines: Vec<String>,
+    paragraph: Vec<String>,
+}
+
+impl TerminalRenderer {
+    fn flush_paragraph(&mut self) {
+        if self.paragraph.is_empty() {
+            return;
+        }
+
+        let text = render_inline(&self.paragraph.join(" "));
+        self.lines.extend(wrap(&text, self.width, "", ""));
+        self.paragraph.clear();
+    }
+
+    fn push_blank(&mut self) {
+        if self.lines.last().is_some_and(|l| !l.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    fn push_heading(&mut self, text: &str) {
+        let text = format!("{ANSI_BOLD}{}{ANSI_RESET}", render_inline(text.trim()));
+        self.lines.extend(wrap(&text, self.width, "", ""));
+    }
+
+    fn push_code(&mut self, line: &str) {
+        self.lines
+            .push(format!("{CODE_INDENT}{ANSI_CODE}{line}{ANSI_RESET}"));
+    }
+
+    fn push_list_item(&mut self, bullet: &str, text: &str) {
+        let first_prefix = format!("  {bullet} ");
+        let rest_prefix = " ".repeat(visible_width(&first_prefix));
+        let text = render_inline(text);
+        self.lines
+            .extend(wrap(&text, self.width, &first_prefix, &rest_prefix));
+    }
+}
+
+/// Renders Markdown to ANSI-styled text wrapped to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut renderer = TerminalRenderer {
+        width,
+        lines: vec![],
+        paragraph: vec![],
+    };
+
+    let mut source_lines = markdown.lines().peekable();
+
+    while let Some(line) = source_lines.next() {
+        let line = line.trim_end();
+
+        if line.trim_start().starts_with("```") {
+            renderer.flush_paragraph();
+            for code_line in source_lines.by_ref() {
+                if code_line.trim_start().starts_with("```") {
+                    break;
+                }
+                renderer.push_code(code_line.trim_end());
+            }
+        } else if line.is_empty() {
+            renderer.flush_paragraph();
+            renderer.push_blank();
+        } else if is_rule(line) {
+            // a rule directly below a paragraph is a setext heading underline.
+            if renderer.paragraph.is_empty() {
+                renderer.lines.push("─".repeat(width));
+            } else {
+                let heading = renderer.paragraph.join(" ");
+                renderer.paragraph.clear();
+                renderer.push_heading(&heading);
+            }
+        } else if let Some(heading) = line.strip_prefix('#') {
+            renderer.flush_paragraph();
+            renderer.push_heading(heading.trim_start_matches('#'));
+        } else if let Some((bullet, text)) = list_item(line) {
+            renderer.flush_paragraph();
+
+            // continuation lines of an item are indented.
+            let mut text = text.to_string();
+            while let Some(next) = source_lines.peek()
+                && next.starts_with("  ")
+                && list_item(next).is_none()
+                && !next.trim().is_empty()
+            {
+                text.push(' ');
+                text.push_str(next.trim());
+                source_lines.next();
+            }
+
+            renderer.push_list_item(&bullet, &text);
+        } else if let Some(code_line) = line.strip_prefix(CODE_INDENT)
+            && renderer.paragraph.is_empty()
+        {
+            renderer.push_code(code_line);
+        } else {
+            renderer.paragraph.push(line.trim().to_string());
+        }
+    }
+
+    renderer.flush_paragraph();
+
+    while renderer.lines.last().is_some_and(String::is_empty) {
+        renderer.lines.pop();
+    }
+
+    renderer.lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, render_inline, strip_ansi, visible_width, wrap};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const CODE: &str = "\x1b[36m";
+
+    #[test]
+    fn measures_visible_width() {
+        assert_eq!(visible_width("abc"), 3);
+        assert_eq!(visible_width(&format!("{ANSI_BOLD}abc{ANSI_RESET}")), 3);
+        assert_eq!(visible_width("• ü"), 3);
+    }
+
+    #[test]
+    fn wraps_words() {
+        let lines = wrap("aaa bbb ccc ddd", 8, "", "");
+        assert_eq!(lines, vec!["aaa bbb", "ccc ddd"]);
+
+        let lines = wrap("aaa bbb ccc", 8, "* ", "  ");
+        assert_eq!(lines, vec!["* aaa", "  bbb", "  ccc"]);
+
+        let lines = wrap("averyveryverylongword", 8, "", "");
+        assert_eq!(lines, vec!["averyveryverylongword"]);
+    }
+
+    #[test]
+    fn renders_inline_styles() {
+        assert_eq!(
+            render_inline("a *b* c"),
+            format!("a {ANSI_RESET}{ANSI_ITALIC}b{ANSI_RESET} c")
+        );
+        assert_eq!(
+            render_inline("**b**"),
+            format!("{ANSI_RESET}{ANSI_BOLD}b{ANSI_RESET}")
+        );
+        assert_eq!(
+            render_inline("floor `0`."),
+            format!("floor {CODE}0{ANSI_RESET}.")
+        );
+        assert_eq!(render_inline("2 * 3 = 6"), "2 * 3 = 6");
+        assert_eq!(render_inline(r"\--- Day 1 ---"), "--- Day 1 ---");
+        assert_eq!(
+            strip_ansi(&render_inline("see [the site](https://adventofcode.com).")),
+            "see the site."
+        );
+    }
+
+    #[test]
+    fn renders_puzzle_description() {
+        let markdown = [
+            r"\--- Day 1: Not Quite Lisp ---",
+            "----------",
+            "",
+            "Santa was hoping for a white Christmas, but his weather machine's *snow* function",
+            "is powered by stars.",
+            "",
+            "For example:",
+            "",
+            "* `(())` and `()()` both result in floor `0`.",
+            "* `(((` and `(()(()(` both",
+            "  result in floor `3`.",
+            "",
+            "```",
+            "  ()())",
+            "```",
+        ]
+        .join("\n");
+
+        let rendered = render(&markdown, 40);
+        let expected = [
+            "--- Day 1: Not Quite Lisp ---",
+            "",
+            "Santa was hoping for a white Christmas,",
+            "but his weather machine's snow function",
+            "is powered by stars.",
+            "",
+            "For example:",
+            "",
+            "  • (()) and ()() both result in floor",
+            "    0.",
+            "  • ((( and (()(()( both result in floor",
+            "    3.",
+            "",
+            "      ()())",
+        ]
+        .join("\n");
+
+        assert_eq!(strip_ansi(&rendered), expected);
+        assert!(rendered.starts_with(ANSI_BOLD));
+    }
+}
@@ -2,6 +2,7 @@ use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
+use crate::template::alloc::AllocStats;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -12,6 +13,8 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub allocs_1: Option<AllocStats>,
+    pub allocs_2: Option<AllocStats>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +54,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -67,6 +70,54 @@ impl Timings {
     }
 }
 
+impl Timing {
+    /// Formatted duration of a part (1 or 2), if it was benched.
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Duration of a part (1 or 2) in nanoseconds, if it was benched.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part).and_then(parse_duration_nanos)
+    }
+
+    /// Allocations of a part (1 or 2), if they were counted.
+    pub fn allocs(&self, part: u8) -> Option<AllocStats> {
+        match part {
+            1 => self.allocs_1,
+            2 => self.allocs_2,
+            _ => None,
+        }
+    }
+
+    /// Allocations of all counted parts.
+    pub fn total_allocs(&self) -> Option<AllocStats> {
+        match (self.allocs_1, self.allocs_2) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parse a duration formatted with `{:.1?}` (e.g. `74.13ms`) into nanoseconds.
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -133,6 +184,9 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert("part_1_allocs".into(), allocs_to_json(value.allocs_1));
+        map.insert("part_2_allocs".into(), allocs_to_json(value.allocs_2));
+
         JsonValue::Object(map)
     }
 }
@@ -166,15 +220,51 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // timings stored before allocations were counted have no allocation keys.
+        let allocs_1 = allocs_from_json(json.get("part_1_allocs"))
+            .ok_or("Expected timing.part_1_allocs to be null or an allocs object.")?;
+        let allocs_2 = allocs_from_json(json.get("part_2_allocs"))
+            .ok_or("Expected timing.part_2_allocs to be null or an allocs object.")?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            allocs_1,
+            allocs_2,
         })
     }
 }
 
+fn allocs_to_json(allocs: Option<AllocStats>) -> JsonValue {
+    let Some(allocs) = allocs else {
+        return JsonValue::Null;
+    };
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    #[allow(clippy::cast_precision_loss)]
+    {
+        map.insert("count".into(), JsonValue::Number(allocs.count as f64));
+        map.insert("bytes".into(), JsonValue::Number(allocs.bytes as f64));
+    }
+    JsonValue::Object(map)
+}
+
+/// `Some(None)` for a missing or null value, `None` if the value is malformed.
+fn allocs_from_json(value: Option<&JsonValue>) -> Option<Option<AllocStats>> {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return Some(None);
+    };
+
+    let map = value.get::<HashMap<String, JsonValue>>()?;
+    let number = |key: &str| map.get(key)?.get::<f64>().map(|n| *n as u64);
+    Some(Some(AllocStats {
+        count: number("count")?,
+        bytes: number("bytes")?,
+    }))
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -191,18 +281,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    allocs_1: None,
+                    allocs_2: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    allocs_1: None,
+                    allocs_2: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    allocs_1: None,
+                    allocs_2: None,
                 },
             ],
         }
@@ -247,6 +343,8 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::alloc::AllocStats;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,6 +364,22 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_allocs() {
+            let allocs = Some(AllocStats {
+                count: 3,
+                bytes: 276,
+            });
+            let mut timings = get_mock_timings();
+            timings.data[0].allocs_1 = allocs;
+
+            let json = JsonValue::from(timings).format().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].allocs(1), allocs);
+            assert_eq!(parsed.data[0].allocs(2), None);
+            assert_eq!(parsed.data[0].total_allocs(), allocs);
+        }
     }
 
     mod is_day_complete {
@@ -282,10 +396,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    allocs_1: None,
+                    allocs_2: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +412,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    allocs_1: None,
+                    allocs_2: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +428,12 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    allocs_1: None,
+                    allocs_2: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -334,6 +454,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    allocs_1: None,
+                    allocs_2: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +476,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    allocs_1: None,
+                    allocs_2: None,
                 }],
             };
             let merged = timings.merge(&other);
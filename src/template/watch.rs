@@ -0,0 +1,202 @@
+/// Module that reruns a solution whenever its sources or data files change.
+/// Files are polled for modifications, so no platform-specific file watching is needed.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use crate::template::terminal_markdown::strip_ansi;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of all watched files.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// The solution, the shared `utils` and every data file of the day, e.g. `data/examples/01-2.txt`.
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![PathBuf::from(format!("src/bin/{day}.rs"))];
+    collect_files(Path::new("src/utils"), &mut files);
+
+    let mut data_files = vec![];
+    collect_files(Path::new("data"), &mut data_files);
+    files.extend(data_files.into_iter().filter(|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&day.to_string()) && name.ends_with(".txt"))
+    }));
+
+    files
+}
+
+fn snapshot(day: Day) -> Snapshot {
+    watched_files(day)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Extracts the answer of every part from the output of a solution run.
+/// Multi-line answers are announced with `▼` and printed on the following lines.
+fn parse_answers(output: &[String]) -> BTreeMap<String, String> {
+    let mut answers = BTreeMap::new();
+    // the runner overwrites intermediate results with `\r`, only the final text matters.
+    let mut lines = output
+        .iter()
+        .map(|l| strip_ansi(l.rsplit('\r').next().unwrap_or_default()))
+        .peekable();
+
+    while let Some(line) = lines.next() {
+        let Some((part, rest)) = line.split_once(": ") else {
+            continue;
+        };
+
+        if !part.starts_with("Part ") {
+            continue;
+        }
+
+        let answer = if rest.starts_with('▼') {
+            let mut answer_lines = vec![];
+            while let Some(next) = lines.next_if(|l| !l.starts_with("Part ")) {
+                answer_lines.push(next);
+            }
+            answer_lines.join("\n")
+        } else {
+            rest.rsplit_once(" (")
+                .map_or(rest, |(answer, _)| answer)
+                .trim()
+                .to_string()
+        };
+
+        answers.insert(part.to_string(), answer);
+    }
+
+    answers
+}
+
+/// Describes how the answers changed between two runs.
+fn diff_answers(
+    previous: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<String> {
+    current
+        .iter()
+        .map(|(part, answer)| match previous.get(part) {
+            Some(old) if old == answer => {
+                format!("{part}: {answer} {ANSI_ITALIC}(unchanged){ANSI_RESET}")
+            }
+            Some(old) => format!("{part}: {old} → {ANSI_BOLD}{answer}{ANSI_RESET}"),
+            None => {
+                format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET} {ANSI_ITALIC}(new){ANSI_RESET}")
+            }
+        })
+        .collect()
+}
+
+/// Calls `run` once and then again after every change to the files of `day`, until interrupted.
+/// `run` returns the output lines of the solution, which are used to report changed answers.
+pub fn watch(day: Day, mut run: impl FnMut() -> Vec<String>) {
+    let mut last_snapshot = snapshot(day);
+    let mut last_answers = parse_answers(&run());
+
+    loop {
+        println!("\n{ANSI_ITALIC}Watching for changes, press Ctrl-C to exit.{ANSI_RESET}");
+
+        while snapshot(day) == last_snapshot {
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        println!("\n{ANSI_BOLD}Change detected, rerunning day {day}{ANSI_RESET}");
+        println!("------");
+
+        let answers = parse_answers(&run());
+        if !answers.is_empty() {
+            println!("\n{ANSI_BOLD}Answers{ANSI_RESET}");
+            for line in diff_answers(&last_answers, &answers) {
+                println!("{line}");
+            }
+            last_answers = answers;
+        }
+
+        // files touched while running (e.g. by the editor) should not trigger another run.
+        last_snapshot = snapshot(day);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use super::{diff_answers, parse_answers, watched_files};
+    use crate::day;
+    use crate::template::terminal_markdown::strip_ansi;
+
+    #[test]
+    fn parses_answers() {
+        let output = [
+            "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)".to_string(),
+            "Part 2: ✖             ".to_string(),
+        ];
+        let answers = parse_answers(&output);
+        assert_eq!(answers["Part 1"], "42");
+        assert_eq!(answers["Part 2"], "✖");
+    }
+
+    #[test]
+    fn parses_multiline_answers() {
+        let output = [
+            "Part 1: ▼  (1.2ms)".to_string(),
+            "#..#".to_string(),
+            ".##.".to_string(),
+            "Part 2: 7 (5.0µs)".to_string(),
+        ];
+        let answers = parse_answers(&output);
+        assert_eq!(answers["Part 1"], "#..#\n.##.");
+        assert_eq!(answers["Part 2"], "7");
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let previous = BTreeMap::from([
+            ("Part 1".to_string(), "42".to_string()),
+            ("Part 2".to_string(), "✖".to_string()),
+        ]);
+        let current = BTreeMap::from([
+            ("Part 1".to_string(), "42".to_string()),
+            ("Part 2".to_string(), "7".to_string()),
+        ]);
+        let diff: Vec<String> = diff_answers(&previous, &current)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert_eq!(diff, vec!["Part 1: 42 (unchanged)", "Part 2: ✖ → 7"]);
+    }
+
+    #[test]
+    fn watches_day_files() {
+        let files = watched_files(day!(8));
+        assert!(files.contains(&PathBuf::from("src/bin/08.rs")));
+        assert!(files.contains(&PathBuf::from("src/utils/mod.rs")));
+        assert!(files.contains(&PathBuf::from("data/examples/08-2.txt")));
+        assert!(!files.contains(&PathBuf::from("data/examples/09.txt")));
+    }
+}
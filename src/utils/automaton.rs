@@ -0,0 +1,129 @@
+use crate::utils::coord_2d::Coord;
+use crate::utils::grid::{Boundary, Grid, Neighborhood};
+
+/// A cellular automaton on a [`Grid`], stepped with a double buffer so that every cell is updated
+/// from the same generation.
+pub struct Automaton<T> {
+    _current: Grid<T>,
+    _next: Grid<T>,
+    _neighborhood: Neighborhood,
+    _boundary: Boundary,
+    _pinned: Vec<(Coord, T)>,
+}
+
+impl<T: Clone> Automaton<T> {
+    pub fn new(grid: Grid<T>, neighborhood: Neighborhood, boundary: Boundary) -> Self {
+        Automaton {
+            _next: grid.clone(),
+            _current: grid,
+            _neighborhood: neighborhood,
+            _boundary: boundary,
+            _pinned: vec![],
+        }
+    }
+
+    /// Sets a cell to `value` and keeps it there after every step, e.g. a stuck light.
+    pub fn pin(&mut self, c: Coord, value: T) {
+        if let Some(cell) = self._current.get_mut(&c) {
+            *cell = value.clone();
+            self._pinned.push((c, value));
+        }
+    }
+
+    /// Advances one generation. `rule` receives a cell and its neighbors and returns the new cell.
+    pub fn step(&mut self, mut rule: impl FnMut(&T, &[&T]) -> T) {
+        let mut neighbors = Vec::with_capacity(8);
+        let width = self._current.width() as i64;
+        let height = self._current.height() as i64;
+
+        for y in 0..height {
+            for x in 0..width {
+                let c = Coord::new(x, y);
+
+                neighbors.clear();
+                neighbors.extend(
+                    self._current
+                        .neighbors(&c, self._neighborhood, self._boundary)
+                        .map(|n| n.value),
+                );
+
+                self._next[c] = rule(&self._current[c], &neighbors);
+            }
+        }
+
+        for (c, value) in &self._pinned {
+            self._next[*c] = value.clone();
+        }
+
+        std::mem::swap(&mut self._current, &mut self._next);
+    }
+
+    pub fn run(&mut self, steps: usize, mut rule: impl FnMut(&T, &[&T]) -> T) {
+        for _ in 0..steps {
+            self.step(&mut rule);
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self._current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self._current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+
+    fn game_of_life(on: &bool, neighbors: &[&bool]) -> bool {
+        let neighbors_on = neighbors.iter().filter(|n| ***n).count();
+        matches!((on, neighbors_on), (true, 2) | (_, 3))
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = Grid::parse(EXAMPLE, |c| c == '#').unwrap();
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, Boundary::Dead);
+
+        automaton.step(game_of_life);
+        assert_eq!(automaton.grid().count_on(), 11);
+
+        automaton.run(3, game_of_life);
+        assert_eq!(automaton.grid().count_on(), 4);
+    }
+
+    #[test]
+    fn test_pinned_cells() {
+        let grid = Grid::parse(EXAMPLE, |c| c == '#').unwrap();
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, Boundary::Dead);
+
+        for (x, y) in [(0, 0), (5, 0), (0, 5), (5, 5)] {
+            automaton.pin(Coord::new(x, y), true);
+        }
+
+        automaton.run(5, game_of_life);
+        assert_eq!(automaton.into_grid().count_on(), 17);
+    }
+
+    #[test]
+    fn test_wrap_boundary() {
+        // a blinker crossing the edge keeps oscillating on a torus.
+        let grid = Grid::parse(".....\n.....\n##..#\n.....\n.....", |c| c == '#').unwrap();
+        let mut automaton = Automaton::new(grid, Neighborhood::Moore, Boundary::Wrap);
+
+        automaton.step(game_of_life);
+        assert_eq!(automaton.grid().count_on(), 3);
+        assert!(automaton.grid()[Coord::new(0, 1)]);
+        assert!(automaton.grid()[Coord::new(0, 3)]);
+        assert!(!automaton.grid()[Coord::new(4, 2)]);
+
+        automaton.step(game_of_life);
+        assert_eq!(automaton.grid().count_on(), 3);
+        assert!(automaton.grid()[Coord::new(4, 2)]);
+        assert!(automaton.grid()[Coord::new(1, 2)]);
+    }
+}
@@ -0,0 +1,463 @@
+use std::fmt::{Display, Write};
+
+use crate::utils::intern::Interner;
+
+pub type Signal = u16;
+
+/// A gate input, either a constant or the signal of another wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Input {
+    Signal(Signal),
+    Wire(usize),
+}
+
+/// What drives a wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gate {
+    Direct(Input),
+    Not(Input),
+    And(Input, Input),
+    Or(Input, Input),
+    LShift(Input, Input),
+    RShift(Input, Input),
+}
+
+impl Gate {
+    fn inputs(self) -> impl Iterator<Item = Input> {
+        let (a, b) = match self {
+            Gate::Direct(a) | Gate::Not(a) => (a, None),
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::LShift(a, b) | Gate::RShift(a, b) => {
+                (a, Some(b))
+            }
+        };
+        std::iter::once(a).chain(b)
+    }
+
+    fn wires(self) -> impl Iterator<Item = usize> {
+        self.inputs().filter_map(|input| match input {
+            Input::Wire(wire) => Some(wire),
+            Input::Signal(_) => None,
+        })
+    }
+
+    fn operator(&self) -> Option<&'static str> {
+        match self {
+            Gate::Direct(_) => None,
+            Gate::Not(_) => Some("NOT"),
+            Gate::And(..) => Some("AND"),
+            Gate::Or(..) => Some("OR"),
+            Gate::LShift(..) => Some("LSHIFT"),
+            Gate::RShift(..) => Some("RSHIFT"),
+        }
+    }
+
+    /// The output for the given input signals, `value` reads the signal of a wire.
+    fn evaluate(&self, value: impl Fn(Input) -> Signal) -> Signal {
+        match *self {
+            Gate::Direct(a) => value(a),
+            Gate::Not(a) => !value(a),
+            Gate::And(a, b) => value(a) & value(b),
+            Gate::Or(a, b) => value(a) | value(b),
+            // shifting by the width or more leaves no bits.
+            Gate::LShift(a, b) => value(a).checked_shl(value(b).into()).unwrap_or(0),
+            Gate::RShift(a, b) => value(a).checked_shr(value(b).into()).unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CircuitError {
+    /// A line that is not a connection, with its 1-based number.
+    InvalidLine { line: usize, text: String },
+    /// A wire that is read but driven by nothing.
+    UndefinedWire(String),
+    /// Wires that depend on each other, each wire is an input of the next and the last of the first.
+    Cycle(Vec<String>),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::InvalidLine { line, text } => {
+                write!(f, "line {line} is not a valid connection: '{text}'.")
+            }
+            CircuitError::UndefinedWire(wire) => {
+                write!(f, "wire '{wire}' is used but never driven.")
+            }
+            CircuitError::Cycle(wires) => {
+                write!(
+                    f,
+                    "wires form a cycle: {} -> {}",
+                    wires.join(" -> "),
+                    wires[0]
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+/// Wires connected by gates, as in 2015 day 7. Wires are interned, gates and signals
+/// are stored in vectors indexed by wire id.
+#[derive(Clone, Debug, Default)]
+pub struct Circuit<'a> {
+    _wires: Interner<'a>,
+    _gates: Vec<Option<Gate>>,
+    _overrides: Vec<Option<Signal>>,
+    /// The signals evaluated so far, indexed by wire id and cleared when the circuit changes.
+    _signals: Vec<Option<Signal>>,
+}
+
+impl<'a> Circuit<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses connections like `x AND y -> d`, one per line.
+    pub fn parse(input: &'a str) -> Result<Self, CircuitError> {
+        let mut circuit = Circuit::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let invalid = || CircuitError::InvalidLine {
+                line: index + 1,
+                text: line.to_string(),
+            };
+
+            let (source, output) = line.split_once(" -> ").ok_or_else(invalid)?;
+            let tokens: Vec<&str> = source.split_whitespace().collect();
+            // numbers are signals, and have to fit into one.
+            let mut input = |token: &'a str| {
+                if token.bytes().all(|b| b.is_ascii_digit()) {
+                    token.parse().map(Input::Signal).map_err(|_| invalid())
+                } else {
+                    Ok(Input::Wire(circuit.wire_id(token)))
+                }
+            };
+
+            let gate = match tokens[..] {
+                [a] => Gate::Direct(input(a)?),
+                ["NOT", a] => Gate::Not(input(a)?),
+                [a, operator, b] => {
+                    let (a, b) = (input(a)?, input(b)?);
+                    match operator {
+                        "AND" => Gate::And(a, b),
+                        "OR" => Gate::Or(a, b),
+                        "LSHIFT" => Gate::LShift(a, b),
+                        "RSHIFT" => Gate::RShift(a, b),
+                        _ => return Err(invalid()),
+                    }
+                }
+                _ => return Err(invalid()),
+            };
+
+            let output = circuit.wire_id(output.trim());
+            circuit.connect(output, gate);
+        }
+
+        Ok(circuit)
+    }
+
+    /// The id of the wire called `name`, which is added if it does not exist yet.
+    pub fn wire_id(&mut self, name: &'a str) -> usize {
+        let id = self._wires.intern(name);
+        if id == self._gates.len() {
+            self._gates.push(None);
+            self._overrides.push(None);
+        }
+        id
+    }
+
+    pub fn wire(&self, name: &str) -> Option<usize> {
+        self._wires.get(name)
+    }
+
+    pub fn wires(&self) -> &Interner<'a> {
+        &self._wires
+    }
+
+    pub fn gate(&self, wire: usize) -> Option<Gate> {
+        self._gates[wire]
+    }
+
+    /// Drives `wire` with `gate`, replacing its previous gate.
+    pub fn connect(&mut self, wire: usize, gate: Gate) {
+        self._gates[wire] = Some(gate);
+        self._signals.clear();
+    }
+
+    /// Forces `wire` to `signal`, ignoring its gate until the override is cleared.
+    pub fn set_override(&mut self, wire: usize, signal: Signal) {
+        self._overrides[wire] = Some(signal);
+        self._signals.clear();
+    }
+
+    pub fn clear_override(&mut self, wire: usize) {
+        self._overrides[wire] = None;
+        self._signals.clear();
+    }
+
+    /// The wires `wire` reads from, none if it is overridden.
+    fn dependencies(&self, wire: usize) -> impl Iterator<Item = usize> + '_ {
+        let gate = match self._overrides[wire] {
+            Some(_) => None,
+            None => self._gates[wire],
+        };
+        gate.into_iter().flat_map(|gate| gate.wires())
+    }
+
+    /// The signals of all wires, indexed by wire id.
+    pub fn evaluate(&mut self) -> Result<Vec<Signal>, CircuitError> {
+        (0..self._gates.len())
+            .map(|wire| self.resolve(wire))
+            .collect()
+    }
+
+    /// The signal on the wire called `name`.
+    pub fn signal(&mut self, name: &str) -> Result<Signal, CircuitError> {
+        let wire = self
+            .wire(name)
+            .ok_or_else(|| CircuitError::UndefinedWire(name.to_string()))?;
+        self.resolve(wire)
+    }
+
+    /// The signal of `wire`, which only evaluates the wires it depends on. The inputs are
+    /// followed with an explicit stack instead of recursion, and signals are kept until the
+    /// circuit changes.
+    fn resolve(&mut self, wire: usize) -> Result<Signal, CircuitError> {
+        self._signals.resize(self._gates.len(), None);
+        if let Some(signal) = self._signals[wire] {
+            return Ok(signal);
+        }
+
+        // the position of each wire on the stack, to find cycles.
+        let mut position = vec![None; self._gates.len()];
+        let mut stack = vec![];
+        self.push_wire(wire, &mut stack, &mut position)?;
+
+        while let Some(&top) = stack.last() {
+            let pending = self
+                .dependencies(top)
+                .find(|input| self._signals[*input].is_none());
+
+            if let Some(input) = pending {
+                if let Some(start) = position[input] {
+                    return Err(self.cycle(&stack[start..]));
+                }
+                self.push_wire(input, &mut stack, &mut position)?;
+                continue;
+            }
+
+            let signal = match (self._overrides[top], self._gates[top]) {
+                (Some(signal), _) => signal,
+                (None, Some(gate)) => gate.evaluate(|input| match input {
+                    Input::Signal(signal) => signal,
+                    Input::Wire(input) => self._signals[input].expect("inputs are evaluated"),
+                }),
+                (None, None) => unreachable!("undefined wires are not pushed"),
+            };
+            self._signals[top] = Some(signal);
+            position[top] = None;
+            stack.pop();
+        }
+
+        Ok(self._signals[wire].expect("wire was evaluated"))
+    }
+
+    fn push_wire(
+        &self,
+        wire: usize,
+        stack: &mut Vec<usize>,
+        position: &mut [Option<usize>],
+    ) -> Result<(), CircuitError> {
+        if self._overrides[wire].is_none() && self._gates[wire].is_none() {
+            return Err(CircuitError::UndefinedWire(
+                self._wires.name(wire).to_string(),
+            ));
+        }
+        position[wire] = Some(stack.len());
+        stack.push(wire);
+        Ok(())
+    }
+
+    /// The cycle of `wires`, where each wire reads from the next and the last from the first.
+    fn cycle(&self, wires: &[usize]) -> CircuitError {
+        // the stack follows inputs, the cycle is reported in signal direction.
+        let cycle = wires
+            .iter()
+            .rev()
+            .map(|wire| self._wires.name(*wire).to_string())
+            .collect();
+        CircuitError::Cycle(cycle)
+    }
+
+    /// The circuit in Graphviz DOT format, with a node per wire and an edge from every input.
+    /// Constant inputs and overrides are part of the node labels.
+    pub fn to_dot(&self) -> String {
+        let name = |wire: usize| self._wires.name(wire);
+        let describe = |input: Input| match input {
+            Input::Signal(signal) => signal.to_string(),
+            Input::Wire(wire) => name(wire).to_string(),
+        };
+
+        let mut dot = String::from("digraph circuit {\n");
+        for wire in 0..self._gates.len() {
+            let expression = match self._gates[wire] {
+                None => String::from("?"),
+                Some(gate) => {
+                    let inputs: Vec<String> = gate.inputs().map(describe).collect();
+                    match (gate.operator(), &inputs[..]) {
+                        (None, [a]) => a.clone(),
+                        (Some(operator), [a]) => format!("{operator} {a}"),
+                        (Some(operator), [a, b]) => format!("{a} {operator} {b}"),
+                        _ => unreachable!("gates have one or two inputs"),
+                    }
+                }
+            };
+
+            let mut label = format!("{} = {expression}", name(wire));
+            if let Some(signal) = self._overrides[wire] {
+                let _ = write!(label, "\\noverridden: {signal}");
+            }
+            let _ = writeln!(dot, "    \"{}\" [label=\"{label}\"];", name(wire));
+
+            for input in self.dependencies(wire) {
+                let _ = writeln!(dot, "    \"{}\" -> \"{}\";", name(input), name(wire));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i";
+
+    #[test]
+    fn test_evaluate() {
+        let mut circuit = Circuit::parse(EXAMPLE).unwrap();
+        let expected = [
+            ("d", 72),
+            ("e", 507),
+            ("f", 492),
+            ("g", 114),
+            ("h", 65412),
+            ("i", 65079),
+            ("x", 123),
+            ("y", 456),
+        ];
+        for (wire, signal) in expected {
+            assert_eq!(circuit.signal(wire), Ok(signal), "wire {wire}");
+        }
+    }
+
+    #[test]
+    fn test_overrides() {
+        // wires are listed before the ones they read from.
+        let mut circuit = Circuit::parse("b -> a\nNOT c -> b\n5 -> c").unwrap();
+        assert_eq!(circuit.signal("a"), Ok(!5));
+
+        let c = circuit.wire("c").unwrap();
+        circuit.set_override(c, 0);
+        assert_eq!(circuit.signal("a"), Ok(u16::MAX));
+
+        circuit.clear_override(c);
+        assert_eq!(circuit.signal("a"), Ok(!5));
+
+        circuit.connect(c, Gate::Direct(Input::Signal(1)));
+        assert_eq!(circuit.signal("a"), Ok(!1));
+    }
+
+    #[test]
+    fn test_deep_chain() {
+        let depth = 100_000;
+        let mut input = String::from("1 -> w0\n");
+        for i in 1..depth {
+            input.push_str(&format!("w{} LSHIFT 0 -> w{i}\n", i - 1));
+        }
+        let mut circuit = Circuit::parse(&input).unwrap();
+        assert_eq!(circuit.signal(&format!("w{}", depth - 1)), Ok(1));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Circuit::parse("1 -> a\n1 XOR 2 -> b").unwrap_err(),
+            CircuitError::InvalidLine {
+                line: 2,
+                text: "1 XOR 2 -> b".to_string()
+            }
+        );
+
+        let mut circuit = Circuit::parse("x AND 1 -> a").unwrap();
+        assert_eq!(
+            circuit.signal("a"),
+            Err(CircuitError::UndefinedWire("x".to_string()))
+        );
+
+        assert_eq!(
+            Circuit::parse("70000 -> a").unwrap_err(),
+            CircuitError::InvalidLine {
+                line: 1,
+                text: "70000 -> a".to_string()
+            }
+        );
+        assert!(Circuit::parse("x LSHIFT 65536 -> a").is_err());
+
+        let mut circuit = Circuit::parse("c -> z\nb -> a\nNOT a -> b\nb -> c\n1 -> d").unwrap();
+        let Err(CircuitError::Cycle(cycle)) = circuit.signal("z") else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(cycle.len(), 2);
+        assert!(cycle.contains(&"a".to_string()) && cycle.contains(&"b".to_string()));
+        assert!(matches!(circuit.evaluate(), Err(CircuitError::Cycle(_))));
+
+        // overriding a wire on the cycle breaks it.
+        let a = circuit.wire("a").unwrap();
+        circuit.set_override(a, 3);
+        assert_eq!(circuit.signal("z"), Ok(!3));
+    }
+
+    #[test]
+    fn test_only_dependencies() {
+        // neither the cycle nor the undefined wire are needed for `d`.
+        let mut circuit =
+            Circuit::parse("b -> a\nNOT a -> b\nx AND 1 -> y\n1 -> c\nc LSHIFT 1 -> d").unwrap();
+        assert_eq!(circuit.signal("d"), Ok(2));
+        assert_eq!(
+            circuit.signal("y"),
+            Err(CircuitError::UndefinedWire("x".to_string()))
+        );
+        assert!(matches!(circuit.signal("a"), Err(CircuitError::Cycle(_))));
+        assert_eq!(circuit.signal("c"), Ok(1));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut circuit = Circuit::parse("123 -> x\nx LSHIFT 2 -> f\nNOT f -> h").unwrap();
+        circuit.set_override(0, 7);
+
+        assert_eq!(
+            circuit.to_dot(),
+            "digraph circuit {
+    \"x\" [label=\"x = 123\\noverridden: 7\"];
+    \"f\" [label=\"f = x LSHIFT 2\"];
+    \"x\" -> \"f\";
+    \"h\" [label=\"h = NOT f\"];
+    \"f\" -> \"h\";
+}
+"
+        );
+    }
+}
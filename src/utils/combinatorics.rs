@@ -0,0 +1,254 @@
+/// All ways to write `total` as an ordered sum of `parts` non-negative integers,
+/// in lexicographic order, e.g. `[0, 2]`, `[1, 1]`, `[2, 0]` for a total of 2 in 2 parts.
+/// There are `C(total + parts - 1, parts - 1)` of them.
+pub fn compositions(total: u32, parts: usize) -> Compositions {
+    let next = match parts {
+        0 => (total == 0).then(Vec::new),
+        _ => {
+            let mut first = vec![0; parts];
+            first[parts - 1] = total;
+            Some(first)
+        }
+    };
+    Compositions { _next: next }
+}
+
+pub struct Compositions {
+    _next: Option<Vec<u32>>,
+}
+
+impl Iterator for Compositions {
+    type Item = Vec<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self._next.take()?;
+
+        // move one unit from the last non-zero part to the part before it,
+        // and put the rest of that part at the end.
+        let mut next = current.clone();
+        if let Some(last_non_zero) = next.iter().rposition(|part| *part > 0)
+            && last_non_zero > 0
+        {
+            let rest = next[last_non_zero];
+            next[last_non_zero] = 0;
+            next[last_non_zero - 1] += 1;
+            *next.last_mut().expect("there are parts") = rest - 1;
+            self._next = Some(next);
+        }
+
+        Some(current)
+    }
+}
+
+/// All ways to write `total` as an unordered sum of positive integers, each as non-increasing
+/// parts, in reverse lexicographic order, e.g. `[3]`, `[2, 1]`, `[1, 1, 1]` for a total of 3.
+pub fn partitions(total: u32) -> Partitions {
+    let first = if total == 0 { vec![] } else { vec![total] };
+    Partitions { _next: Some(first) }
+}
+
+pub struct Partitions {
+    _next: Option<Vec<u32>>,
+}
+
+impl Iterator for Partitions {
+    type Item = Vec<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self._next.take()?;
+
+        // decrease the last part above 1 and spread the ones after it
+        // as evenly as allowed by the decreased part.
+        let mut next = current.clone();
+        if let Some(index) = next.iter().rposition(|part| *part > 1) {
+            let mut rest = (next.len() - index) as u32;
+            next.truncate(index + 1);
+            next[index] -= 1;
+            let largest = next[index];
+            while rest > 0 {
+                let part = rest.min(largest);
+                next.push(part);
+                rest -= part;
+            }
+            self._next = Some(next);
+        }
+
+        Some(current)
+    }
+}
+
+/// The subsets of `values` that sum to `target`, as bitmasks of indexes into `values`.
+/// Depth first with pruning on the partial and remaining sums, so only subsets that can
+/// still reach the target are explored. Panics for more than 64 values.
+pub fn subsets_with_sum(values: &[u32], target: u64) -> SubsetSums<'_> {
+    assert!(values.len() <= 64, "at most 64 values fit in a bitmask");
+
+    // remaining[i]: the sum of values[i..].
+    let mut remaining = vec![0; values.len() + 1];
+    for (i, value) in values.iter().enumerate().rev() {
+        remaining[i] = remaining[i + 1] + u64::from(*value);
+    }
+
+    SubsetSums {
+        _values: values,
+        _target: target,
+        _remaining: remaining,
+        _stack: vec![(0, 0, 0)],
+    }
+}
+
+pub struct SubsetSums<'a> {
+    _values: &'a [u32],
+    _target: u64,
+    _remaining: Vec<u64>,
+    /// Partial subsets as (next index, mask, sum).
+    _stack: Vec<(usize, u64, u64)>,
+}
+
+impl Iterator for SubsetSums<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((index, mask, sum)) = self._stack.pop() {
+            if sum > self._target || sum + self._remaining[index] < self._target {
+                continue;
+            }
+            if index == self._values.len() {
+                return Some(mask);
+            }
+
+            let value = u64::from(self._values[index]);
+            self._stack
+                .push((index + 1, mask | 1 << index, sum + value));
+            self._stack.push((index + 1, mask, sum));
+        }
+        None
+    }
+}
+
+/// The subsets of size `k` of `0..n`, as bitmasks in increasing order. Panics for `n` above 64.
+pub fn combinations(n: usize, k: usize) -> Combinations {
+    assert!(n <= 64, "at most 64 elements fit in a bitmask");
+
+    let next = (k <= n).then(|| (1u128 << k) - 1);
+    Combinations {
+        _limit: 1 << n,
+        _next: next,
+    }
+}
+
+pub struct Combinations {
+    _limit: u128,
+    _next: Option<u128>,
+}
+
+impl Iterator for Combinations {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mask = self._next.take()?;
+
+        // Gosper's hack: the next larger integer with the same number of bits set.
+        if mask != 0 {
+            let lowest = mask & mask.wrapping_neg();
+            let ripple = mask + lowest;
+            let next = (((ripple ^ mask) >> 2) / lowest) | ripple;
+            self._next = (next < self._limit).then_some(next);
+        }
+
+        Some(mask as u64)
+    }
+}
+
+/// The indexes of the bits set in `mask`, from low to high.
+pub fn indexes(mut mask: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
+        let index = mask.trailing_zeros() as usize;
+        mask &= mask - 1;
+        Some(index)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binomial(n: u64, k: u64) -> u64 {
+        (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn test_compositions() {
+        assert_eq!(
+            compositions(2, 2).collect::<Vec<_>>(),
+            vec![vec![0, 2], vec![1, 1], vec![2, 0]]
+        );
+        assert_eq!(compositions(5, 1).collect::<Vec<_>>(), vec![vec![5]]);
+        assert_eq!(compositions(0, 3).collect::<Vec<_>>(), vec![vec![0, 0, 0]]);
+        assert_eq!(compositions(0, 0).count(), 1);
+        assert_eq!(compositions(1, 0).count(), 0);
+
+        let all: Vec<_> = compositions(100, 4).collect();
+        assert_eq!(all.len() as u64, binomial(103, 3));
+        assert!(all.iter().all(|parts| parts.iter().sum::<u32>() == 100));
+        assert!(all.is_sorted());
+    }
+
+    #[test]
+    fn test_partitions() {
+        assert_eq!(
+            partitions(4).collect::<Vec<_>>(),
+            vec![
+                vec![4],
+                vec![3, 1],
+                vec![2, 2],
+                vec![2, 1, 1],
+                vec![1, 1, 1, 1]
+            ]
+        );
+        assert_eq!(partitions(0).collect::<Vec<_>>(), vec![Vec::<u32>::new()]);
+
+        // p(n) from OEIS A000041.
+        let counts: Vec<_> = (1..=10).map(|n| partitions(n).count()).collect();
+        assert_eq!(counts, vec![1, 2, 3, 5, 7, 11, 15, 22, 30, 42]);
+        assert_eq!(partitions(50).count(), 204_226);
+        assert!(partitions(20).all(|parts| parts.is_sorted_by(|a, b| a >= b)));
+    }
+
+    #[test]
+    fn test_subsets_with_sum() {
+        // 2015 day 17 example.
+        let containers = [20, 15, 10, 5, 5];
+        let mut subsets: Vec<_> = subsets_with_sum(&containers, 25).collect();
+        subsets.sort();
+        assert_eq!(subsets, vec![0b00110, 0b01001, 0b10001, 0b11010]);
+
+        let smallest = subsets.iter().map(|mask| mask.count_ones()).min();
+        assert_eq!(smallest, Some(2));
+        assert_eq!(
+            indexes(0b01101).map(|i| containers[i]).collect::<Vec<_>>(),
+            vec![20, 10, 5]
+        );
+
+        assert_eq!(subsets_with_sum(&[0, 1], 1).count(), 2);
+        assert_eq!(subsets_with_sum(&[], 0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(subsets_with_sum(&[3, 4], 5).count(), 0);
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(4, 2).collect::<Vec<_>>(),
+            vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]
+        );
+        assert_eq!(combinations(3, 0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(combinations(3, 4).count(), 0);
+        assert_eq!(combinations(64, 64).collect::<Vec<_>>(), vec![u64::MAX]);
+        assert_eq!(combinations(64, 1).last(), Some(1 << 63));
+        assert_eq!(combinations(20, 7).count() as u64, binomial(20, 7));
+        assert!(combinations(20, 7).all(|mask| mask.count_ones() == 7));
+    }
+}
@@ -1,15 +1,48 @@
-use anyhow::Context;
+use anyhow::{Context, bail};
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 use std::str::FromStr;
 
+/// A position or offset on a 2D plane. Follows the grid convention: `x` grows to the right and
+/// `y` grows downwards, so [`Direction::Up`] decreases `y`.
+/// Ordered by `x` first, then by `y`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Coord {
     pub x: i64,
     pub y: i64,
 }
 
 impl Coord {
-    pub fn new(x: i64, y: i64) -> Self {
+    pub const ORIGIN: Coord = Coord { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
         Coord { x, y }
     }
+
+    pub fn manhattan_distance(&self, other: &Coord) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance when diagonal steps are allowed, i.e. the number of king moves.
+    pub fn chebyshev_distance(&self, other: &Coord) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The neighboring coordinate in `direction`.
+    pub fn step(self, direction: Direction) -> Coord {
+        self + direction.offset()
+    }
+
+    /// The 4 orthogonal neighbors, clockwise starting with the one above.
+    pub fn neighbors4(self) -> impl Iterator<Item = Coord> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
 }
 
 impl FromStr for Coord {
@@ -24,3 +57,204 @@ impl FromStr for Coord {
         Ok(Coord::new(x, y))
     }
 }
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Coord) -> Self::Output {
+        Coord::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Coord) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Coord) -> Self::Output {
+        Coord::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Coord {
+    fn sub_assign(&mut self, rhs: Coord) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Coord {
+    type Output = Coord;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Coord::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Self::Output {
+        Coord::new(-self.x, -self.y)
+    }
+}
+
+impl Add<Direction> for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self.step(rhs)
+    }
+}
+
+impl AddAssign<Direction> for Coord {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = self.step(rhs);
+    }
+}
+
+/// One of the 4 orthogonal directions on a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting with [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn offset(self) -> Coord {
+        match self {
+            Direction::Up => Coord::new(0, -1),
+            Direction::Right => Coord::new(1, 0),
+            Direction::Down => Coord::new(0, 1),
+            Direction::Left => Coord::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    /// Parses arrows (`^`, `>`, `v`, `<`) and compass points (`N`, `E`, `S`, `W`).
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' => Ok(Direction::Up),
+            '>' | 'E' => Ok(Direction::Right),
+            'v' | 'S' => Ok(Direction::Down),
+            '<' | 'W' => Ok(Direction::Left),
+            _ => bail!("Bad format, `{value}` is not a direction."),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => bail!("Bad format, expected a single character but got `{s}`."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Coord::from_str("3,-4").unwrap(), Coord::new(3, -4));
+        assert!(Coord::from_str("3;4").is_err());
+        assert_eq!(Coord::new(3, -4).to_string(), "3,-4");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Coord::new(1, 2);
+        let b = Coord::new(4, -2);
+        assert_eq!(a + b, Coord::new(5, 0));
+        assert_eq!(a - b, Coord::new(-3, 4));
+        assert_eq!(a * 3, Coord::new(3, 6));
+        assert_eq!(-a, Coord::new(-1, -2));
+
+        let mut c = a;
+        c += b;
+        c -= Coord::new(1, 1);
+        assert_eq!(c, Coord::new(4, -1));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Coord::new(1, 2);
+        let b = Coord::new(4, -2);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.manhattan_distance(&a), 0);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut coords = vec![Coord::new(1, 0), Coord::new(0, 5), Coord::new(0, -1)];
+        coords.sort();
+        assert_eq!(
+            coords,
+            vec![Coord::new(0, -1), Coord::new(0, 5), Coord::new(1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.opposite(), Direction::Left);
+
+        let c = Coord::ORIGIN + Direction::Up + Direction::Right;
+        assert_eq!(c, Coord::new(1, -1));
+        assert_eq!(
+            Coord::ORIGIN.neighbors4().collect::<Vec<_>>(),
+            vec![
+                Coord::new(0, -1),
+                Coord::new(1, 0),
+                Coord::new(0, 1),
+                Coord::new(-1, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_direction() {
+        assert_eq!(Direction::try_from('^').unwrap(), Direction::Up);
+        assert_eq!(Direction::try_from('S').unwrap(), Direction::Down);
+        assert_eq!(Direction::from_str("<").unwrap(), Direction::Left);
+        assert_eq!(Direction::from_str("E").unwrap(), Direction::Right);
+        assert!(Direction::try_from('x').is_err());
+        assert!(Direction::from_str("NE").is_err());
+    }
+}
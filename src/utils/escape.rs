@@ -0,0 +1,379 @@
+use std::fmt::Display;
+
+/// The escape sequences understood inside a quoted literal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EscapeSet {
+    /// `\\`, `\"` and `\xNN`, as in 2015 day 8.
+    #[default]
+    Basic,
+    /// The escapes of Rust byte and string literals: `\\ \" \' \n \r \t \0 \xNN \u{N..}`.
+    Rust,
+    /// The escapes of JSON strings: `\\ \" \/ \b \f \n \r \t \uNNNN`, with surrogate pairs.
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EscapeErrorKind {
+    /// The literal does not start with a quote, or is never closed.
+    MissingQuote,
+    /// An unescaped quote before the end of the literal.
+    UnexpectedQuote,
+    /// A backslash followed by a character that is not an escape in the set.
+    UnknownEscape(char),
+    /// Missing or invalid hex digits after `\x` or `\u`.
+    InvalidHex,
+    /// A `\u` escape that is not a valid character, e.g. a lone surrogate.
+    InvalidUnicode,
+}
+
+/// An invalid literal, `position` is the byte offset of the problem in the literal.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EscapeError {
+    pub position: usize,
+    pub kind: EscapeErrorKind,
+}
+
+impl Display for EscapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid literal at byte {}: ", self.position)?;
+        match self.kind {
+            EscapeErrorKind::MissingQuote => write!(f, "missing quote."),
+            EscapeErrorKind::UnexpectedQuote => write!(f, "unescaped quote inside the literal."),
+            EscapeErrorKind::UnknownEscape(c) => write!(f, "unknown escape sequence '\\{c}'."),
+            EscapeErrorKind::InvalidHex => write!(f, "invalid hex digits."),
+            EscapeErrorKind::InvalidUnicode => write!(f, "escape is not a valid character."),
+        }
+    }
+}
+
+impl std::error::Error for EscapeError {}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+fn hex_value(bytes: &[u8]) -> Option<u32> {
+    if bytes.is_empty() {
+        return None;
+    }
+    bytes
+        .iter()
+        .try_fold(0, |acc, b| Some(acc * 16 + char::from(*b).to_digit(16)?))
+}
+
+/// Decodes the quoted `literal` and hands every decoded piece to `emit`, without allocating.
+fn decode_each(
+    literal: &str,
+    set: EscapeSet,
+    mut emit: impl FnMut(&[u8]),
+) -> Result<(), EscapeError> {
+    let bytes = literal.as_bytes();
+    let error = |position, kind| Err(EscapeError { position, kind });
+
+    if bytes.first() != Some(&b'"') {
+        return error(0, EscapeErrorKind::MissingQuote);
+    }
+
+    let mut i = 1;
+    loop {
+        // fast path: everything up to the next quote or backslash is copied as is.
+        let Some(offset) = bytes[i..].iter().position(|&b| b == b'"' || b == b'\\') else {
+            return error(bytes.len(), EscapeErrorKind::MissingQuote);
+        };
+        if offset > 0 {
+            emit(&bytes[i..i + offset]);
+        }
+        i += offset;
+
+        if bytes[i] == b'"' {
+            if i + 1 != bytes.len() {
+                return error(i, EscapeErrorKind::UnexpectedQuote);
+            }
+            return Ok(());
+        }
+
+        let start = i;
+        let Some(&escape) = bytes.get(i + 1) else {
+            return error(bytes.len(), EscapeErrorKind::MissingQuote);
+        };
+        i += 2;
+
+        let simple = match (escape, set) {
+            (b'\\' | b'"', _) => Some(escape),
+            (b'\'', EscapeSet::Rust) | (b'/', EscapeSet::Json) => Some(escape),
+            (b'n', EscapeSet::Rust | EscapeSet::Json) => Some(b'\n'),
+            (b'r', EscapeSet::Rust | EscapeSet::Json) => Some(b'\r'),
+            (b't', EscapeSet::Rust | EscapeSet::Json) => Some(b'\t'),
+            (b'0', EscapeSet::Rust) => Some(0),
+            (b'b', EscapeSet::Json) => Some(0x08),
+            (b'f', EscapeSet::Json) => Some(0x0c),
+            _ => None,
+        };
+        if let Some(byte) = simple {
+            emit(&[byte]);
+            continue;
+        }
+
+        let code = match (escape, set) {
+            (b'x', EscapeSet::Basic | EscapeSet::Rust) => {
+                let value = bytes.get(i..i + 2).and_then(hex_value);
+                let Some(value) = value else {
+                    return error(start, EscapeErrorKind::InvalidHex);
+                };
+                i += 2;
+                // `\xNN` is a single byte, not a character.
+                emit(&[value as u8]);
+                continue;
+            }
+            (b'u', EscapeSet::Rust) => {
+                let digits = bytes
+                    .get(i..)
+                    .and_then(|rest| rest.strip_prefix(b"{"))
+                    .and_then(|rest| rest.iter().position(|&b| b == b'}').map(|end| &rest[..end]))
+                    .filter(|digits| digits.len() <= 6);
+                let Some(value) = digits.and_then(hex_value) else {
+                    return error(start, EscapeErrorKind::InvalidHex);
+                };
+                i += digits.map_or(0, <[u8]>::len) + 2;
+                value
+            }
+            (b'u', EscapeSet::Json) => {
+                let Some(high) = bytes.get(i..i + 4).and_then(hex_value) else {
+                    return error(start, EscapeErrorKind::InvalidHex);
+                };
+                i += 4;
+
+                if (0xd800..0xdc00).contains(&high) {
+                    // a high surrogate has to be followed by an escaped low surrogate.
+                    let low = bytes
+                        .get(i..i + 6)
+                        .and_then(|next| next.strip_prefix(b"\\u"))
+                        .and_then(hex_value)
+                        .filter(|low| (0xdc00..0xe000).contains(low));
+                    let Some(low) = low else {
+                        return error(start, EscapeErrorKind::InvalidUnicode);
+                    };
+                    i += 6;
+                    0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                } else {
+                    high
+                }
+            }
+            _ => {
+                let c = literal[start + 1..].chars().next().unwrap_or_default();
+                return error(start, EscapeErrorKind::UnknownEscape(c));
+            }
+        };
+
+        let Some(c) = char::from_u32(code) else {
+            return error(start, EscapeErrorKind::InvalidUnicode);
+        };
+        emit(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+}
+
+/// The bytes the quoted `literal` stands for.
+pub fn unescape(literal: &str, set: EscapeSet) -> Result<Vec<u8>, EscapeError> {
+    let mut decoded = Vec::with_capacity(literal.len());
+    decode_each(literal, set, |piece| decoded.extend_from_slice(piece))?;
+    Ok(decoded)
+}
+
+/// Like [`unescape`], for literals that decode to valid UTF-8.
+pub fn unescape_str(literal: &str, set: EscapeSet) -> anyhow::Result<String> {
+    Ok(String::from_utf8(unescape(literal, set)?)?)
+}
+
+/// The number of bytes the quoted `literal` stands for, without allocating.
+pub fn unescaped_len(literal: &str, set: EscapeSet) -> Result<usize, EscapeError> {
+    let mut len = 0;
+    decode_each(literal, set, |piece| len += piece.len())?;
+    Ok(len)
+}
+
+/// Encodes `bytes` as a quoted literal and hands every piece to `emit`, without allocating.
+fn encode_each(bytes: &[u8], set: EscapeSet, mut emit: impl FnMut(&[u8])) {
+    let escape_byte = |b: u8| {
+        [
+            b'\\',
+            b'x',
+            HEX_DIGITS[usize::from(b >> 4)],
+            HEX_DIGITS[usize::from(b & 15)],
+        ]
+    };
+
+    emit(b"\"");
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            let named = match (c, set) {
+                ('\\', _) => Some(b"\\\\"),
+                ('"', _) => Some(b"\\\""),
+                ('\n', EscapeSet::Rust | EscapeSet::Json) => Some(b"\\n"),
+                ('\r', EscapeSet::Rust | EscapeSet::Json) => Some(b"\\r"),
+                ('\t', EscapeSet::Rust | EscapeSet::Json) => Some(b"\\t"),
+                ('\0', EscapeSet::Rust) => Some(b"\\0"),
+                ('\u{8}', EscapeSet::Json) => Some(b"\\b"),
+                ('\u{c}', EscapeSet::Json) => Some(b"\\f"),
+                _ => None,
+            };
+            if let Some(named) = named {
+                emit(named);
+                continue;
+            }
+
+            let mut utf8 = [0; 4];
+            let encoded = c.encode_utf8(&mut utf8).as_bytes();
+            match set {
+                EscapeSet::Basic if !(' '..='~').contains(&c) => {
+                    encoded.iter().for_each(|&b| emit(&escape_byte(b)));
+                }
+                EscapeSet::Rust if c.is_control() => {
+                    let code = c as u32;
+                    emit(b"\\u{");
+                    for shift in (0..=(31 - code.leading_zeros()) / 4).rev() {
+                        emit(&[HEX_DIGITS[(code >> (shift * 4)) as usize & 15]]);
+                    }
+                    emit(b"}");
+                }
+                EscapeSet::Json if u32::from(c) < 0x20 => {
+                    let code = u32::from(c) as u8;
+                    emit(b"\\u00");
+                    emit(&[
+                        HEX_DIGITS[usize::from(code >> 4)],
+                        HEX_DIGITS[usize::from(code & 15)],
+                    ]);
+                }
+                _ => emit(encoded),
+            }
+        }
+
+        match set {
+            // JSON can only hold characters, invalid UTF-8 becomes the replacement character.
+            EscapeSet::Json if !chunk.invalid().is_empty() => emit(b"\\ufffd"),
+            _ => chunk.invalid().iter().for_each(|&b| emit(&escape_byte(b))),
+        }
+    }
+    emit(b"\"");
+}
+
+/// `bytes` as a quoted literal, the inverse of [`unescape`].
+pub fn escape(bytes: &[u8], set: EscapeSet) -> String {
+    let mut encoded = Vec::with_capacity(bytes.len() + 2);
+    encode_each(bytes, set, |piece| encoded.extend_from_slice(piece));
+    String::from_utf8(encoded).expect("escapes are ASCII and other characters are copied whole")
+}
+
+/// The length of [`escape`]'s result, without allocating.
+pub fn escaped_len(bytes: &[u8], set: EscapeSet) -> usize {
+    let mut len = 0;
+    encode_each(bytes, set, |piece| len += piece.len());
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(position: usize, kind: EscapeErrorKind) -> EscapeError {
+        EscapeError { position, kind }
+    }
+
+    #[test]
+    fn test_unescape_basic() {
+        let set = EscapeSet::Basic;
+        assert_eq!(unescape(r#""""#, set), Ok(vec![]));
+        assert_eq!(unescape(r#""abc""#, set), Ok(b"abc".to_vec()));
+        assert_eq!(unescape(r#""aaa\"aaa""#, set), Ok(b"aaa\"aaa".to_vec()));
+        assert_eq!(unescape(r#""\x27\\\xff""#, set), Ok(b"'\\\xff".to_vec()));
+        assert_eq!(unescaped_len(r#""\x27""#, set), Ok(1));
+    }
+
+    #[test]
+    fn test_unescape_rust_and_json() {
+        assert_eq!(
+            unescape_str(r#""a\n\t\'\0\u{1F600}""#, EscapeSet::Rust).unwrap(),
+            "a\n\t'\0\u{1F600}"
+        );
+        assert_eq!(
+            unescape_str(r#""\/\b\f\u00e9\ud83d\ude00""#, EscapeSet::Json).unwrap(),
+            "/\u{8}\u{c}\u{e9}\u{1F600}"
+        );
+        assert_eq!(unescaped_len(r#""\u00e9""#, EscapeSet::Json), Ok(2));
+    }
+
+    #[test]
+    fn test_errors() {
+        let set = EscapeSet::Basic;
+        assert_eq!(
+            unescape("abc", set),
+            Err(error(0, EscapeErrorKind::MissingQuote))
+        );
+        assert_eq!(
+            unescape(r#""abc"#, set),
+            Err(error(4, EscapeErrorKind::MissingQuote))
+        );
+        assert_eq!(
+            unescape(r#""ab\""#, set),
+            Err(error(5, EscapeErrorKind::MissingQuote))
+        );
+        assert_eq!(
+            unescape(r#""a"b""#, set),
+            Err(error(2, EscapeErrorKind::UnexpectedQuote))
+        );
+        assert_eq!(
+            unescape(r#""a\n""#, set),
+            Err(error(2, EscapeErrorKind::UnknownEscape('n')))
+        );
+        assert_eq!(
+            unescape(r#""\x2g""#, set),
+            Err(error(1, EscapeErrorKind::InvalidHex))
+        );
+        assert_eq!(
+            unescape(r#""\x27""#, EscapeSet::Json),
+            Err(error(1, EscapeErrorKind::UnknownEscape('x')))
+        );
+        assert_eq!(
+            unescape(r#""\ud83d""#, EscapeSet::Json),
+            Err(error(1, EscapeErrorKind::InvalidUnicode))
+        );
+        assert_eq!(
+            unescape(r#""\u{110000}""#, EscapeSet::Rust),
+            Err(error(1, EscapeErrorKind::InvalidUnicode))
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(b"", EscapeSet::Basic), r#""""#);
+        assert_eq!(escape(b"aaa\"aaa", EscapeSet::Basic), r#""aaa\"aaa""#);
+        assert_eq!(
+            escape(b"\\x27\n\xff", EscapeSet::Basic),
+            r#""\\x27\x0a\xff""#
+        );
+        assert_eq!(
+            escape("é\u{7f}\t".as_bytes(), EscapeSet::Rust),
+            r#""é\u{7f}\t""#
+        );
+        assert_eq!(escape(b"\x01/\xff", EscapeSet::Json), r#""\u0001/\ufffd""#);
+        assert_eq!(escaped_len(br#""\x27""#, EscapeSet::Basic), 11);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let samples: [&[u8]; 4] = [
+            b"plain",
+            b"\"q\" \\ \x00\x1f\x7f",
+            "ünï 😀".as_bytes(),
+            b"\xc3\x28",
+        ];
+
+        for set in [EscapeSet::Basic, EscapeSet::Rust, EscapeSet::Json] {
+            for sample in samples {
+                if set == EscapeSet::Json && std::str::from_utf8(sample).is_err() {
+                    continue;
+                }
+                let escaped = escape(sample, set);
+                assert_eq!(unescape(&escaped, set).as_deref(), Ok(sample), "{escaped}");
+                assert_eq!(escaped_len(sample, set), escaped.len());
+            }
+        }
+    }
+}
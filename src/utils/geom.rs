@@ -11,44 +11,309 @@ impl PositionalLine {
     }
 }
 
+/// An axis-aligned rectangle of coordinates, both corners included.
+/// The corners are normalized on construction: `top_corner` holds the smallest and
+/// `bottom_corner` the largest `x` and `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct PositionalRectangle {
-    pub top_corner: Coord,
-    pub bottom_corner: Coord,
+    _top_corner: Coord,
+    _bottom_corner: Coord,
 }
 
 impl PositionalRectangle {
-    pub fn new(top_corner: Coord, bottom_corner: Coord) -> Self {
+    pub fn new(corner_a: Coord, corner_b: Coord) -> Self {
         PositionalRectangle {
-            top_corner,
-            bottom_corner,
+            _top_corner: Coord::new(corner_a.x.min(corner_b.x), corner_a.y.min(corner_b.y)),
+            _bottom_corner: Coord::new(corner_a.x.max(corner_b.x), corner_a.y.max(corner_b.y)),
         }
     }
 
-    pub fn area(&self) -> usize {
-        self.length() * self.width()
+    pub fn top_corner(&self) -> Coord {
+        self._top_corner
     }
-    
-    pub fn intersection_area(&self, _other: PositionalRectangle) -> Option<usize> {
-        todo!()
+
+    pub fn bottom_corner(&self) -> Coord {
+        self._bottom_corner
     }
 
-    pub fn intersects_with(&self, _other: PositionalRectangle) -> bool {
-        todo!()
+    pub fn area(&self) -> usize {
+        self.length() * self.width()
     }
 
+    /// Extent along the `x` axis.
     pub fn length(&self) -> usize {
-        ((self.top_corner.x - self.bottom_corner.x).abs() + 1) as usize
+        (self._bottom_corner.x - self._top_corner.x + 1) as usize
     }
 
+    /// Extent along the `y` axis.
     pub fn width(&self) -> usize {
-        ((self.top_corner.y - self.bottom_corner.y).abs() + 1) as usize
+        (self._bottom_corner.y - self._top_corner.y + 1) as usize
+    }
+
+    pub fn contains(&self, c: &Coord) -> bool {
+        (self._top_corner.x..=self._bottom_corner.x).contains(&c.x)
+            && (self._top_corner.y..=self._bottom_corner.y).contains(&c.y)
+    }
+
+    pub fn contains_rectangle(&self, other: &PositionalRectangle) -> bool {
+        self.contains(&other._top_corner) && self.contains(&other._bottom_corner)
+    }
+
+    pub fn intersection(&self, other: &PositionalRectangle) -> Option<PositionalRectangle> {
+        let top_corner = Coord::new(
+            self._top_corner.x.max(other._top_corner.x),
+            self._top_corner.y.max(other._top_corner.y),
+        );
+        let bottom_corner = Coord::new(
+            self._bottom_corner.x.min(other._bottom_corner.x),
+            self._bottom_corner.y.min(other._bottom_corner.y),
+        );
+
+        (top_corner.x <= bottom_corner.x && top_corner.y <= bottom_corner.y).then_some(
+            PositionalRectangle {
+                _top_corner: top_corner,
+                _bottom_corner: bottom_corner,
+            },
+        )
+    }
+
+    pub fn intersection_area(&self, other: &PositionalRectangle) -> Option<usize> {
+        self.intersection(other).map(|r| r.area())
+    }
+
+    pub fn intersects_with(&self, other: &PositionalRectangle) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn bounding_box(&self, other: &PositionalRectangle) -> PositionalRectangle {
+        PositionalRectangle {
+            _top_corner: Coord::new(
+                self._top_corner.x.min(other._top_corner.x),
+                self._top_corner.y.min(other._top_corner.y),
+            ),
+            _bottom_corner: Coord::new(
+                self._bottom_corner.x.max(other._bottom_corner.x),
+                self._bottom_corner.y.max(other._bottom_corner.y),
+            ),
+        }
+    }
+
+    /// Splits into the columns left of `x` and the columns from `x` on.
+    pub fn split_at_x(&self, x: i64) -> (Option<PositionalRectangle>, Option<PositionalRectangle>) {
+        let left = (x > self._top_corner.x).then(|| {
+            PositionalRectangle::new(
+                self._top_corner,
+                Coord::new((x - 1).min(self._bottom_corner.x), self._bottom_corner.y),
+            )
+        });
+        let right = (x <= self._bottom_corner.x).then(|| {
+            PositionalRectangle::new(
+                Coord::new(x.max(self._top_corner.x), self._top_corner.y),
+                self._bottom_corner,
+            )
+        });
+        (left, right)
+    }
+
+    /// Splits into the rows above `y` and the rows from `y` on.
+    pub fn split_at_y(&self, y: i64) -> (Option<PositionalRectangle>, Option<PositionalRectangle>) {
+        let top = (y > self._top_corner.y).then(|| {
+            PositionalRectangle::new(
+                self._top_corner,
+                Coord::new(self._bottom_corner.x, (y - 1).min(self._bottom_corner.y)),
+            )
+        });
+        let bottom = (y <= self._bottom_corner.y).then(|| {
+            PositionalRectangle::new(
+                Coord::new(self._top_corner.x, y.max(self._top_corner.y)),
+                self._bottom_corner,
+            )
+        });
+        (top, bottom)
+    }
+
+    /// The part of `self` not covered by `other`, as up to 4 disjoint rectangles.
+    pub fn subtract(&self, other: &PositionalRectangle) -> Vec<PositionalRectangle> {
+        let Some(cut) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let mut pieces = Vec::with_capacity(4);
+
+        // full-width bands above and below the cut, then the remains left and right of it.
+        let (top, rest) = self.split_at_y(cut._top_corner.y);
+        pieces.extend(top);
+
+        if let Some(rest) = rest {
+            let (middle, bottom) = rest.split_at_y(cut._bottom_corner.y + 1);
+            pieces.extend(bottom);
+
+            if let Some(middle) = middle {
+                let (left, rest) = middle.split_at_x(cut._top_corner.x);
+                pieces.extend(left);
+
+                if let Some(rest) = rest {
+                    let (_, right) = rest.split_at_x(cut._bottom_corner.x + 1);
+                    pieces.extend(right);
+                }
+            }
+        }
+
+        pieces
+    }
+
+    /// The area covered by either rectangle, as disjoint rectangles.
+    pub fn union(&self, other: &PositionalRectangle) -> Vec<PositionalRectangle> {
+        let mut pieces = vec![*self];
+        pieces.extend(other.subtract(self));
+        pieces
+    }
+
+    /// All contained coordinates, row by row.
+    pub fn iter_coords(&self) -> impl Iterator<Item = Coord> {
+        let (top, bottom) = (self._top_corner, self._bottom_corner);
+        (top.y..=bottom.y).flat_map(move |y| (top.x..=bottom.x).map(move |x| Coord::new(x, y)))
     }
 
     pub fn iter_horizontal_lines(&self) -> impl Iterator<Item = PositionalLine> {
-        (self.top_corner.y..=self.bottom_corner.y).map(|delta_y| {
-            let start = Coord::new(self.top_corner.x, delta_y);
-            let end = Coord::new(self.bottom_corner.x, delta_y);
+        let (top, bottom) = (self._top_corner, self._bottom_corner);
+        (top.y..=bottom.y).map(move |delta_y| {
+            let start = Coord::new(top.x, delta_y);
+            let end = Coord::new(bottom.x, delta_y);
             PositionalLine::new(start, end)
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn rect(x1: i64, y1: i64, x2: i64, y2: i64) -> PositionalRectangle {
+        PositionalRectangle::new(Coord::new(x1, y1), Coord::new(x2, y2))
+    }
+
+    fn coord_set(rects: &[PositionalRectangle]) -> HashSet<(i64, i64)> {
+        rects
+            .iter()
+            .flat_map(|r| r.iter_coords())
+            .map(|c| (c.x, c.y))
+            .collect()
+    }
+
+    /// Deterministic pseudo-random rectangles within `0..12` on both axes.
+    fn random_rects(count: usize) -> Vec<PositionalRectangle> {
+        let mut state: u64 = 0x2015_1206;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) % 12) as i64
+        };
+
+        (0..count)
+            .map(|_| rect(next(), next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalization() {
+        let r = rect(5, 1, 2, 4);
+        assert_eq!(r.top_corner(), Coord::new(2, 1));
+        assert_eq!(r.bottom_corner(), Coord::new(5, 4));
+        assert_eq!(r.length(), 4);
+        assert_eq!(r.width(), 4);
+        assert_eq!(r.area(), 16);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = rect(0, 0, 4, 4);
+        assert_eq!(a.intersection(&rect(2, 3, 8, 8)), Some(rect(2, 3, 4, 4)));
+        assert_eq!(a.intersection_area(&rect(4, 4, 8, 8)), Some(1));
+        assert_eq!(a.intersection_area(&rect(5, 0, 8, 8)), None);
+        assert!(a.intersects_with(&rect(1, 1, 2, 2)));
+        assert!(!a.intersects_with(&rect(0, 5, 4, 5)));
+    }
+
+    #[test]
+    fn test_containment() {
+        let a = rect(0, 0, 4, 4);
+        assert!(a.contains(&Coord::new(4, 0)));
+        assert!(!a.contains(&Coord::new(5, 0)));
+        assert!(a.contains_rectangle(&rect(1, 1, 4, 4)));
+        assert!(!a.contains_rectangle(&rect(1, 1, 5, 4)));
+    }
+
+    #[test]
+    fn test_split() {
+        let a = rect(0, 0, 4, 2);
+        assert_eq!(
+            a.split_at_x(2),
+            (Some(rect(0, 0, 1, 2)), Some(rect(2, 0, 4, 2)))
+        );
+        assert_eq!(a.split_at_x(0), (None, Some(a)));
+        assert_eq!(a.split_at_x(5), (Some(a), None));
+        assert_eq!(
+            a.split_at_y(1),
+            (Some(rect(0, 0, 4, 0)), Some(rect(0, 1, 4, 2)))
+        );
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = rect(0, 0, 4, 4);
+        assert_eq!(a.subtract(&rect(6, 6, 8, 8)), vec![a]);
+        assert_eq!(a.subtract(&rect(0, 0, 8, 8)), vec![]);
+        assert_eq!(
+            a.subtract(&rect(1, 1, 3, 3)),
+            vec![
+                rect(0, 0, 4, 0),
+                rect(0, 4, 4, 4),
+                rect(0, 1, 0, 3),
+                rect(4, 1, 4, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_coords() {
+        let coords: Vec<(i64, i64)> = rect(1, 1, 2, 2).iter_coords().map(|c| (c.x, c.y)).collect();
+        assert_eq!(coords, vec![(1, 1), (2, 1), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_properties() {
+        let rects = random_rects(40);
+
+        for a in &rects {
+            let cells_a = coord_set(&[*a]);
+            assert_eq!(cells_a.len(), a.area());
+
+            for b in &rects {
+                let cells_b = coord_set(&[*b]);
+                let common = cells_a.intersection(&cells_b).count();
+
+                assert_eq!(a.intersection_area(b).unwrap_or(0), common);
+                assert_eq!(a.intersects_with(b), common > 0);
+                assert_eq!(a.contains_rectangle(b), cells_b.is_subset(&cells_a));
+
+                // the pieces of a subtraction are disjoint and cover exactly `a \ b`.
+                let pieces = a.subtract(b);
+                let piece_area: usize = pieces.iter().map(|p| p.area()).sum();
+                let expected: HashSet<_> = cells_a.difference(&cells_b).copied().collect();
+                assert_eq!(piece_area, expected.len());
+                assert_eq!(coord_set(&pieces), expected);
+
+                let union = a.union(b);
+                let union_area: usize = union.iter().map(|p| p.area()).sum();
+                assert_eq!(union_area, cells_a.union(&cells_b).count());
+
+                let bounds = a.bounding_box(b);
+                assert!(bounds.contains_rectangle(a) && bounds.contains_rectangle(b));
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use std::ops::Add;
+
+use itertools::Itertools;
+
+use crate::utils::intern::Interner;
+
+/// The most nodes [`Graph::hamiltonian_path`] and [`Graph::hamiltonian_cycle`] accept,
+/// their tables then take about 25 MB for 64-bit weights.
+pub const MAX_HELD_KARP_NODES: usize = 16;
+
+/// Whether the cheapest or the most expensive tour is wanted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Goal {
+    Minimize,
+    Maximize,
+}
+
+impl Goal {
+    pub(crate) fn is_better<W: Ord>(self, candidate: W, current: Option<W>) -> bool {
+        match (self, current) {
+            (_, None) => true,
+            (Goal::Minimize, Some(current)) => candidate < current,
+            (Goal::Maximize, Some(current)) => candidate > current,
+        }
+    }
+}
+
+/// A path or cycle through every node, `nodes` are ids in visiting order.
+/// A cycle does not repeat its first node at the end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tour<W> {
+    pub cost: W,
+    pub nodes: Vec<usize>,
+}
+
+/// A directed graph with named nodes, stored as adjacency matrix.
+/// Nodes get dense ids in the order they are first seen.
+#[derive(Clone, Debug)]
+pub struct Graph<'a, W> {
+    _nodes: Interner<'a>,
+    _weights: Vec<Vec<Option<W>>>,
+}
+
+impl<'a, W> Default for Graph<'a, W> {
+    fn default() -> Self {
+        Graph {
+            _nodes: Interner::new(),
+            _weights: vec![],
+        }
+    }
+}
+
+impl<'a, W: Copy + Ord + Default + Add<Output = W>> Graph<'a, W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of the node called `name`, which is added if it does not exist yet.
+    pub fn node(&mut self, name: &'a str) -> usize {
+        let id = self._nodes.intern(name);
+        if id == self._weights.len() {
+            self._weights.iter_mut().for_each(|row| row.push(None));
+            self._weights.push(vec![None; id + 1]);
+        }
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self._nodes.get(name)
+    }
+
+    pub fn name(&self, id: usize) -> &'a str {
+        self._nodes.name(id)
+    }
+
+    pub fn nodes(&self) -> &Interner<'a> {
+        &self._nodes
+    }
+
+    pub fn len(&self) -> usize {
+        self._nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self._nodes.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        self._weights[from][to] = Some(weight);
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, weight: W) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn weight(&self, from: usize, to: usize) -> Option<W> {
+        self._weights[from][to]
+    }
+
+    /// The nodes reachable from `from` with a single edge, with the weight of the edge.
+    pub fn neighbors(&self, from: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self._weights[from]
+            .iter()
+            .enumerate()
+            .filter_map(|(to, weight)| Some((to, (*weight)?)))
+    }
+
+    /// The best path visiting every node exactly once, with any start and end.
+    /// Exact, using the Held–Karp dynamic programming in O(2^n · n²) time and O(2^n · n) memory.
+    /// Panics for more than [`MAX_HELD_KARP_NODES`] nodes, where the tables would need hundreds
+    /// of megabytes. `None` if there is no such path.
+    pub fn hamiltonian_path(&self, goal: Goal) -> Option<Tour<W>> {
+        self.held_karp(goal, false)
+    }
+
+    /// The best cycle visiting every node exactly once, like [`Graph::hamiltonian_path`]
+    /// and with the same limit of [`MAX_HELD_KARP_NODES`] nodes.
+    pub fn hamiltonian_cycle(&self, goal: Goal) -> Option<Tour<W>> {
+        self.held_karp(goal, true)
+    }
+
+    /// Like [`Graph::hamiltonian_path`] by trying all orders, in O(n! · n) time and O(n) memory.
+    pub fn hamiltonian_path_brute_force(&self, goal: Goal) -> Option<Tour<W>> {
+        let n = self.len();
+        self.best_permutation(goal, (0..n).permutations(n), false)
+    }
+
+    /// Like [`Graph::hamiltonian_cycle`] by trying all orders, in O(n! · n) time and O(n) memory.
+    pub fn hamiltonian_cycle_brute_force(&self, goal: Goal) -> Option<Tour<W>> {
+        let n = self.len();
+        if n == 0 {
+            return None;
+        }
+
+        // rotations of a cycle are the same cycle, so it always starts at node 0.
+        let orders = (1..n).permutations(n - 1).map(|rest| {
+            let mut order = Vec::with_capacity(n);
+            order.push(0);
+            order.extend(rest);
+            order
+        });
+        self.best_permutation(goal, orders, true)
+    }
+
+    /// The cost of visiting `nodes` in order, `None` if an edge is missing.
+    pub fn tour_cost(&self, nodes: &[usize], cycle: bool) -> Option<W> {
+        let closing = nodes
+            .last()
+            .zip(nodes.first())
+            .filter(|_| cycle && nodes.len() > 1);
+
+        nodes
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| (*a, *b))
+            .chain(closing.map(|(a, b)| (*a, *b)))
+            .try_fold(W::default(), |cost, (a, b)| Some(cost + self.weight(a, b)?))
+    }
+
+    fn best_permutation(
+        &self,
+        goal: Goal,
+        orders: impl Iterator<Item = Vec<usize>>,
+        cycle: bool,
+    ) -> Option<Tour<W>> {
+        let mut best: Option<Tour<W>> = None;
+
+        for nodes in orders {
+            if let Some(cost) = self.tour_cost(&nodes, cycle)
+                && goal.is_better(cost, best.as_ref().map(|tour| tour.cost))
+            {
+                best = Some(Tour { cost, nodes });
+            }
+        }
+
+        best
+    }
+
+    fn held_karp(&self, goal: Goal, cycle: bool) -> Option<Tour<W>> {
+        const NO_PARENT: usize = usize::MAX;
+
+        let n = self.len();
+        assert!(
+            n <= MAX_HELD_KARP_NODES,
+            "Held–Karp handles at most {MAX_HELD_KARP_NODES} nodes"
+        );
+        if n == 0 {
+            return None;
+        }
+
+        // best[mask * n + last]: the best cost of visiting the nodes in `mask`, ending at `last`.
+        let full = (1usize << n) - 1;
+        let mut best: Vec<Option<W>> = vec![None; (full + 1) * n];
+        let mut parent = vec![NO_PARENT; (full + 1) * n];
+
+        // a cycle visits every node, so it can start at node 0.
+        let starts = if cycle { 0..1 } else { 0..n };
+        for start in starts {
+            best[(1 << start) * n + start] = Some(W::default());
+        }
+
+        for mask in 1..=full {
+            for last in 0..n {
+                let Some(cost) = best[mask * n + last] else {
+                    continue;
+                };
+
+                for (next, weight) in self.neighbors(last) {
+                    if mask & (1 << next) != 0 {
+                        continue;
+                    }
+
+                    let index = (mask | 1 << next) * n + next;
+                    if goal.is_better(cost + weight, best[index]) {
+                        best[index] = Some(cost + weight);
+                        parent[index] = last;
+                    }
+                }
+            }
+        }
+
+        let mut end: Option<(usize, W)> = None;
+        for last in 0..n {
+            let Some(mut cost) = best[full * n + last] else {
+                continue;
+            };
+
+            if cycle && n > 1 {
+                let Some(closing) = self.weight(last, 0) else {
+                    continue;
+                };
+                cost = cost + closing;
+            }
+
+            if goal.is_better(cost, end.map(|(_, cost)| cost)) {
+                end = Some((last, cost));
+            }
+        }
+
+        let (mut node, cost) = end?;
+        let mut mask = full;
+        let mut nodes = Vec::with_capacity(n);
+        loop {
+            nodes.push(node);
+            let previous = parent[mask * n + node];
+            if previous == NO_PARENT {
+                break;
+            }
+            mask ^= 1 << node;
+            node = previous;
+        }
+        nodes.reverse();
+
+        Some(Tour { cost, nodes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day_9_example() -> Graph<'static, u64> {
+        let mut graph = Graph::new();
+        for (a, b, distance) in [
+            ("London", "Dublin", 464),
+            ("London", "Belfast", 518),
+            ("Dublin", "Belfast", 141),
+        ] {
+            let (a, b) = (graph.node(a), graph.node(b));
+            graph.add_undirected_edge(a, b, distance);
+        }
+        graph
+    }
+
+    /// A complete directed graph with pseudo-random weights.
+    fn random_graph(n: usize, seed: u64) -> Graph<'static, i64> {
+        const NAMES: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
+
+        let mut state = seed;
+        let mut graph = Graph::new();
+        for name in &NAMES[..n] {
+            graph.node(name);
+        }
+        for from in 0..n {
+            for to in (0..n).filter(|to| *to != from) {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                graph.add_edge(from, to, (state >> 33) as i64 % 100 - 30);
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_interning() {
+        let graph = day_9_example();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.id("Belfast"), Some(2));
+        assert_eq!(graph.id("Paris"), None);
+        assert_eq!(graph.name(1), "Dublin");
+        assert_eq!(
+            graph.neighbors(2).collect::<Vec<_>>(),
+            vec![(0, 518), (1, 141)]
+        );
+    }
+
+    #[test]
+    fn test_paths() {
+        let graph = day_9_example();
+
+        let shortest = graph.hamiltonian_path(Goal::Minimize).unwrap();
+        assert_eq!(shortest.cost, 605);
+        assert_eq!(graph.tour_cost(&shortest.nodes, false), Some(605));
+
+        let longest = graph.hamiltonian_path(Goal::Maximize).unwrap();
+        assert_eq!(longest.cost, 982);
+        // Dublin -> London -> Belfast, in either direction.
+        assert_eq!(graph.name(longest.nodes[1]), "London");
+    }
+
+    #[test]
+    fn test_cycles() {
+        let mut graph: Graph<u32> = Graph::new();
+        let (a, b, c) = (graph.node("a"), graph.node("b"), graph.node("c"));
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, 1);
+        graph.add_edge(c, a, 1);
+        graph.add_edge(a, c, 10);
+        graph.add_edge(c, b, 10);
+        graph.add_edge(b, a, 10);
+
+        let tour = graph.hamiltonian_cycle(Goal::Minimize).unwrap();
+        assert_eq!(
+            tour,
+            Tour {
+                cost: 3,
+                nodes: vec![a, b, c]
+            }
+        );
+        assert_eq!(graph.hamiltonian_cycle(Goal::Maximize).unwrap().cost, 30);
+    }
+
+    #[test]
+    fn test_missing_edges() {
+        let mut graph: Graph<u32> = Graph::new();
+        let (a, b, c) = (graph.node("a"), graph.node("b"), graph.node("c"));
+        graph.add_undirected_edge(a, b, 1);
+        graph.add_undirected_edge(b, c, 1);
+
+        assert_eq!(graph.hamiltonian_path(Goal::Minimize).unwrap().cost, 2);
+        assert_eq!(graph.hamiltonian_cycle(Goal::Minimize), None);
+        assert_eq!(graph.hamiltonian_cycle_brute_force(Goal::Minimize), None);
+        assert_eq!(Graph::<u32>::new().hamiltonian_path(Goal::Minimize), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Held–Karp handles at most 16 nodes")]
+    fn test_held_karp_too_many_nodes() {
+        let names: Vec<String> = (0..17).map(|i| i.to_string()).collect();
+        let mut graph: Graph<u32> = Graph::new();
+        for name in &names {
+            graph.node(name);
+        }
+        graph.hamiltonian_path(Goal::Minimize);
+    }
+
+    #[test]
+    fn test_held_karp_matches_brute_force() {
+        for n in 1..=7 {
+            for seed in 0..5 {
+                let graph = random_graph(n, seed);
+                for goal in [Goal::Minimize, Goal::Maximize] {
+                    let exact = graph.hamiltonian_path(goal).unwrap();
+                    let brute = graph.hamiltonian_path_brute_force(goal).unwrap();
+                    assert_eq!(exact.cost, brute.cost);
+                    assert_eq!(graph.tour_cost(&exact.nodes, false), Some(exact.cost));
+
+                    let exact = graph.hamiltonian_cycle(goal).unwrap();
+                    let brute = graph.hamiltonian_cycle_brute_force(goal).unwrap();
+                    assert_eq!(exact.cost, brute.cost);
+                    assert_eq!(graph.tour_cost(&exact.nodes, true), Some(exact.cost));
+                }
+            }
+        }
+    }
+}
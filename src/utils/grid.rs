@@ -1,5 +1,7 @@
 use super::coord_2d::Coord;
 use crate::utils::geom::{PositionalLine, PositionalRectangle};
+use anyhow::bail;
+use std::fmt::Display;
 use std::ops::{Index, IndexMut};
 
 #[inline]
@@ -40,43 +42,224 @@ impl<'grid, T> MutableGridElement<'grid, T> {
     }
 }
 
-// TODO: this is a square grid, make it dimensionally generic
-pub struct Grid<T: Default, const SIZE: usize> {
-    _array: [T; SIZE],
+const ORTHOGONAL_OFFSETS: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const MOORE_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// Which cells around a cell count as its neighbors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The 4 cells sharing an edge.
+    Orthogonal,
+    /// The 8 cells sharing an edge or a corner.
+    Moore,
+}
+
+impl Neighborhood {
+    fn offsets(self) -> &'static [(i64, i64)] {
+        match self {
+            Neighborhood::Orthogonal => &ORTHOGONAL_OFFSETS,
+            Neighborhood::Moore => &MOORE_OFFSETS,
+        }
+    }
+}
+
+/// How neighbors are looked up for cells at the edges of a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    /// Cells outside the grid do not exist.
+    #[default]
+    Dead,
+    /// The grid is a torus, the last column neighbors the first one and so on.
+    Wrap,
+}
+
+/// A rectangular grid of `width` × `height` cells stored row by row on the heap.
+/// `Coord { x: 0, y: 0 }` is the top left cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    _cells: Vec<T>,
     _width: usize,
+    _height: usize,
 }
 
-impl<T: Default + Copy, const SIZE: usize> Grid<T, SIZE> {
-    pub fn new_empty() -> Self {
+impl<T: Default + Clone> Grid<T> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, T::default())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
         Grid {
-            _array: [T::default(); SIZE],
-            _width: SIZE.isqrt(),
+            _cells: vec![value; width * height],
+            _width: width,
+            _height: height,
         }
     }
 
-    pub fn new_boxed() -> Box<Grid<T, SIZE>> {
-        // this hack is necessary because Rust lacks placement new (creating a type directly on a
-        // memory location), since Grid is stack allocated, a massive one may cause a stack
-        // overflow, so this is required to construct it directly on the heap
-        let layout = std::alloc::Layout::new::<Grid<T, SIZE>>();
-        let ptr = unsafe { std::alloc::alloc(layout) as *mut Grid<T, SIZE> };
-        let mut grid = unsafe { Box::from_raw(ptr) };
+    pub fn fill_in_rectangle(&mut self, rect: PositionalRectangle, value: T) {
+        rect.iter_horizontal_lines()
+            .for_each(|l| self.fill_in_horizontal_line(l, value.clone()));
+    }
 
-        grid._array.fill(T::default());
-        grid._width = SIZE.isqrt();
+    pub fn fill_in_horizontal_line(&mut self, line: PositionalLine, value: T) {
+        let (begin_idx, end_idx) = self.line_indices(line);
+        self._cells[begin_idx..=end_idx].fill(value);
+    }
+}
 
-        grid
+impl<T> Grid<T> {
+    /// Builds a grid from lines of text, converting every character with `func`.
+    /// All lines must have the same length.
+    pub fn parse(input: &str, mut func: impl FnMut(char) -> T) -> anyhow::Result<Self> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+            cells.extend(line.chars().map(&mut func));
+            let line_width = cells.len() - len_before;
+
+            match width {
+                None => width = Some(line_width),
+                Some(w) if w != line_width => {
+                    bail!("Bad format, line {y} has {line_width} cells instead of {w}.")
+                }
+                _ => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Grid {
+            _cells: cells,
+            _width: width.unwrap_or(0),
+            _height: height,
+        })
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = GridElement<T>> {
-        self._array
-            .iter()
-            .enumerate()
-            .map(|(i, value)| GridElement::new(i, *value, self._width))
+    pub fn width(&self) -> usize {
+        self._width
+    }
+
+    pub fn height(&self) -> usize {
+        self._height
+    }
+
+    pub fn in_bounds(&self, c: &Coord) -> bool {
+        (0..self._width as i64).contains(&c.x) && (0..self._height as i64).contains(&c.y)
+    }
+
+    pub fn get(&self, c: &Coord) -> Option<&T> {
+        if self.in_bounds(c) {
+            Some(&self._cells[coord_to_grid_index(*c, self._width)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, c: &Coord) -> Option<&mut T> {
+        if self.in_bounds(c) {
+            Some(&mut self._cells[coord_to_grid_index(*c, self._width)])
+        } else {
+            None
+        }
+    }
+
+    /// The index of the cell at `c`, panics if `c` lies outside the grid.
+    /// Without the check, a coordinate outside a row would silently alias a cell of another row.
+    fn checked_index(&self, c: Coord) -> usize {
+        assert!(
+            self.in_bounds(&c),
+            "coordinate {c} is outside the {}x{} grid",
+            self._width,
+            self._height
+        );
+        coord_to_grid_index(c, self._width)
+    }
+
+    /// The indices of the first and the last cell of a horizontal `line`, which has to lie
+    /// within the grid.
+    fn line_indices(&self, line: PositionalLine) -> (usize, usize) {
+        (self.checked_index(line.start), self.checked_index(line.end))
+    }
+
+    /// Positions of the neighbors of `c`, in clockwise order. Each neighbor is listed once and
+    /// a cell is never its own neighbor, which can happen when wrapping a grid narrower than 3.
+    pub fn neighbor_positions(
+        &self,
+        c: &Coord,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+    ) -> impl Iterator<Item = Coord> + '_ {
+        let c = *c;
+        let (width, height) = (self._width as i64, self._height as i64);
+        let offsets = neighborhood.offsets();
+
+        let position = move |(dx, dy): (i64, i64)| match boundary {
+            Boundary::Dead => Coord::new(c.x + dx, c.y + dy),
+            Boundary::Wrap => {
+                Coord::new((c.x + dx).rem_euclid(width), (c.y + dy).rem_euclid(height))
+            }
+        };
+
+        // an empty grid has no cells to wrap around to.
+        let offsets = if self._cells.is_empty() { &[] } else { offsets };
+        offsets.iter().enumerate().filter_map(move |(i, offset)| {
+            let neighbor = position(*offset);
+            let is_new = boundary == Boundary::Dead
+                || (neighbor != c && offsets[..i].iter().all(|o| position(*o) != neighbor));
+            (is_new && self.in_bounds(&neighbor)).then_some(neighbor)
+        })
+    }
+
+    pub fn neighbors(
+        &self,
+        c: &Coord,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+    ) -> impl Iterator<Item = GridElement<&T>> {
+        self.neighbor_positions(c, neighborhood, boundary)
+            .map(|position| {
+                let index = coord_to_grid_index(position, self._width);
+                GridElement::new(index, &self._cells[index], self._width)
+            })
+    }
+
+    /// The up to 4 neighbors sharing an edge with `c`.
+    pub fn neighbors4(&self, c: &Coord) -> impl Iterator<Item = GridElement<&T>> {
+        self.neighbors(c, Neighborhood::Orthogonal, Boundary::Dead)
+    }
+
+    /// The up to 8 neighbors sharing an edge or a corner with `c`.
+    pub fn neighbors8(&self, c: &Coord) -> impl Iterator<Item = GridElement<&T>> {
+        self.neighbors(c, Neighborhood::Moore, Boundary::Dead)
+    }
+
+    pub fn count_neighbors(
+        &self,
+        c: &Coord,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+        pred: impl Fn(&T) -> bool,
+    ) -> usize {
+        self.neighbors(c, neighborhood, boundary)
+            .filter(|n| pred(n.value))
+            .count()
     }
 
     pub fn iter_mut(&'_ mut self) -> impl Iterator<Item = MutableGridElement<'_, T>> {
-        self._array
+        self._cells
             .iter_mut()
             .enumerate()
             .map(|(i, value)| MutableGridElement::new(i, value, self._width))
@@ -93,55 +276,390 @@ impl<T: Default + Copy, const SIZE: usize> Grid<T, SIZE> {
         line: PositionalLine,
         func: &mut F,
     ) {
-        let begin_idx = coord_to_grid_index(line.start, self._width);
-        let end_idx = coord_to_grid_index(line.end, self._width);
-        self._array[begin_idx..=end_idx].iter_mut().for_each(func);
-    }
-
-    pub fn fill_in_rectangle(&mut self, rect: PositionalRectangle, value: T) {
-        rect.iter_horizontal_lines()
-            .for_each(|l| self.fill_in_horizontal_line(l, value));
+        let (begin_idx, end_idx) = self.line_indices(line);
+        self._cells[begin_idx..=end_idx].iter_mut().for_each(func);
     }
+}
 
-    pub fn fill_in_horizontal_line(&mut self, line: PositionalLine, value: T) {
-        let begin_idx = coord_to_grid_index(line.start, self._width);
-        let end_idx = coord_to_grid_index(line.end, self._width);
-        self._array[begin_idx..=end_idx].fill(value);
+impl<T: Copy> Grid<T> {
+    pub fn iter(&self) -> impl Iterator<Item = GridElement<T>> {
+        self._cells
+            .iter()
+            .enumerate()
+            .map(|(i, value)| GridElement::new(i, *value, self._width))
     }
 }
 
-impl<T: Default + Copy, const SIZE: usize> Index<Coord> for Grid<T, SIZE> {
+impl<T> Index<Coord> for Grid<T> {
     type Output = T;
 
     fn index(&self, index: Coord) -> &Self::Output {
-        &self._array[coord_to_grid_index(index, self._width)]
+        &self._cells[self.checked_index(index)]
     }
 }
 
-impl<T: Default + Copy, const SIZE: usize> IndexMut<Coord> for Grid<T, SIZE> {
+impl<T> IndexMut<Coord> for Grid<T> {
     fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
-        &mut self._array[coord_to_grid_index(index, self._width)]
+        let index = self.checked_index(index);
+        &mut self._cells[index]
     }
 }
 
-impl<const SIZE: usize> Grid<bool, SIZE> {
+impl Grid<bool> {
     pub fn toggle_in_rectangle(&mut self, rect: PositionalRectangle) {
         rect.iter_horizontal_lines()
             .for_each(|l| self.toggle_in_horizontal_line(l));
     }
 
     pub fn toggle_in_horizontal_line(&mut self, line: PositionalLine) {
-        let begin_idx = coord_to_grid_index(line.start, self._width);
-        let end_idx = coord_to_grid_index(line.end, self._width);
-        self._array[begin_idx..=end_idx]
+        let (begin_idx, end_idx) = self.line_indices(line);
+        self._cells[begin_idx..=end_idx]
             .iter_mut()
             .for_each(|b| *b = !*b);
     }
 
     pub fn count_on(&self) -> usize {
-        self._array.iter().filter(|b| **b).count()
+        self._cells.iter().filter(|b| **b).count()
     }
     pub fn count_off(&self) -> usize {
-        self._array.iter().filter(|b| !**b).count()
+        self._cells.iter().filter(|b| !**b).count()
+    }
+
+    /// Encodes the grid as a plain PBM image, lit cells are black.
+    pub fn to_pbm(&self) -> String {
+        let rows = self
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|b| if *b { "1" } else { "0" })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("P1\n{} {}\n{rows}\n", self._width, self._height)
+    }
+}
+
+impl Display for Grid<bool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|b| if *b { '#' } else { '.' }))
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|c| *c))
+    }
+}
+
+impl<T> Grid<T> {
+    fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a size of 0, an empty grid has no rows anyway.
+        self._cells.chunks(self._width.max(1))
+    }
+
+    /// Draws the grid row by row, converting every cell to a character with `func`.
+    pub fn render(&self, mut func: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut func).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The smallest rectangle containing all cells matching `pred`.
+    pub fn bounding_box(&self, mut pred: impl FnMut(&T) -> bool) -> Option<PositionalRectangle> {
+        self._cells
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| pred(value))
+            .map(|(i, _)| {
+                let c = grid_index_to_coord(i, self._width);
+                PositionalRectangle::new(c, c)
+            })
+            .reduce(|bounds, cell| bounds.bounding_box(&cell))
+    }
+
+    /// Encodes the grid as a binary PPM image with one pixel per cell colored by `func`.
+    pub fn to_ppm(&self, mut func: impl FnMut(&T) -> [u8; 3]) -> Vec<u8> {
+        let mut image = format!("P6\n{} {}\n255\n", self._width, self._height).into_bytes();
+        image.extend(self._cells.iter().flat_map(&mut func));
+        image
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Copies the cells within `rect`, which has to lie within the grid.
+    pub fn sub_grid(&self, rect: PositionalRectangle) -> Grid<T> {
+        let mut cells = Vec::with_capacity(rect.area());
+        for line in rect.iter_horizontal_lines() {
+            let (begin_idx, end_idx) = self.line_indices(line);
+            cells.extend_from_slice(&self._cells[begin_idx..=end_idx]);
+        }
+
+        Grid {
+            _cells: cells,
+            _width: rect.length(),
+            _height: rect.width(),
+        }
+    }
+
+    /// Crops the grid to the bounding box of the cells matching `pred`.
+    /// Returns an empty grid if no cell matches.
+    pub fn crop(&self, pred: impl FnMut(&T) -> bool) -> Grid<T> {
+        match self.bounding_box(pred) {
+            Some(bounds) => self.sub_grid(bounds),
+            None => Grid {
+                _cells: vec![],
+                _width: 0,
+                _height: 0,
+            },
+        }
+    }
+}
+
+impl<T: Clone + Default> Grid<T> {
+    /// Builds a grid from ASCII art, e.g. an indented string literal in a test.
+    /// Blank lines around the picture and common indentation are removed,
+    /// shorter lines are padded with `T::default()`.
+    pub fn parse_art(art: &str, mut func: impl FnMut(char) -> T) -> Self {
+        let lines: Vec<&str> = art.lines().skip_while(|l| l.trim().is_empty()).collect();
+        let lines = &lines[..lines
+            .iter()
+            .rposition(|l| !l.trim().is_empty())
+            .map_or(0, |i| i + 1)];
+
+        let indentation = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let lines: Vec<&str> = lines
+            .iter()
+            .map(|l| l.get(indentation..).unwrap_or_default().trim_end())
+            .collect();
+
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut grid = Grid::new(width, lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid[Coord::new(x as i64, y as i64)] = func(c);
+            }
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("#..\n.#.", |c| c == '#').unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert!(grid[Coord::new(0, 0)]);
+        assert!(grid[Coord::new(1, 1)]);
+        assert!(!grid[Coord::new(2, 1)]);
+        assert_eq!(grid.count_on(), 2);
+
+        assert!(Grid::parse("#..\n.#", |c| c == '#').is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = Grid::<i32>::new(4, 2);
+        assert_eq!(grid.get(&Coord::new(3, 1)), Some(&0));
+        assert_eq!(grid.get(&Coord::new(4, 1)), None);
+        assert_eq!(grid.get(&Coord::new(3, 2)), None);
+        assert_eq!(grid.get(&Coord::new(-1, 0)), None);
+
+        *grid.get_mut(&Coord::new(3, 1)).unwrap() = 5;
+        assert_eq!(grid[Coord::new(3, 1)], 5);
+        assert!(grid.get_mut(&Coord::new(0, -1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate 4,0 is outside the 4x2 grid")]
+    fn test_index_outside_row() {
+        // would read (0, 1) without the bounds check.
+        let grid = Grid::<i32>::new(4, 2);
+        let _ = grid[Coord::new(4, 0)];
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate -1,0 is outside the 4x2 grid")]
+    fn test_index_negative() {
+        let mut grid = Grid::<i32>::new(4, 2);
+        grid[Coord::new(-1, 0)] = 1;
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate 4,0 is outside the 4x2 grid")]
+    fn test_line_outside_row() {
+        // would fill (0, 1) without the bounds check.
+        let mut grid = Grid::<bool>::new(4, 2);
+        grid.fill_in_horizontal_line(
+            PositionalLine::new(Coord::new(2, 0), Coord::new(4, 0)),
+            true,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate 1,2 is outside the 4x2 grid")]
+    fn test_sub_grid_outside() {
+        let grid = Grid::<i32>::new(4, 2);
+        grid.sub_grid(PositionalRectangle::new(Coord::new(1, 1), Coord::new(3, 2)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::parse("123\n456\n789", |c| c.to_digit(10).unwrap()).unwrap();
+        let values = |it: &mut dyn Iterator<Item = GridElement<&u32>>| {
+            it.map(|n| *n.value).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            values(&mut grid.neighbors4(&Coord::new(1, 1))),
+            vec![2, 6, 8, 4]
+        );
+        assert_eq!(values(&mut grid.neighbors4(&Coord::new(0, 0))), vec![2, 4]);
+        assert_eq!(
+            values(&mut grid.neighbors8(&Coord::new(1, 1))),
+            vec![1, 2, 3, 6, 9, 8, 7, 4]
+        );
+        assert_eq!(
+            values(&mut grid.neighbors8(&Coord::new(2, 2))),
+            vec![5, 6, 8]
+        );
+        assert_eq!(
+            values(&mut grid.neighbors(
+                &Coord::new(0, 0),
+                Neighborhood::Orthogonal,
+                Boundary::Wrap
+            )),
+            vec![7, 2, 4, 3]
+        );
+
+        let corner = grid.neighbors8(&Coord::new(0, 0)).next().unwrap();
+        assert_eq!((corner.position.x, corner.position.y), (1, 0));
+    }
+
+    #[test]
+    fn test_wrap_small_grids() {
+        let grid = Grid::parse("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        let values = |neighborhood| {
+            grid.neighbors(&Coord::new(0, 0), neighborhood, Boundary::Wrap)
+                .map(|n| *n.value)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values(Neighborhood::Orthogonal), vec![3, 2]);
+        assert_eq!(values(Neighborhood::Moore), vec![4, 3, 2]);
+
+        let row = Grid::parse("123", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(
+            row.count_neighbors(
+                &Coord::new(1, 0),
+                Neighborhood::Moore,
+                Boundary::Wrap,
+                |_| true
+            ),
+            2
+        );
+
+        let empty = Grid::<bool>::new(0, 0);
+        assert_eq!(
+            empty
+                .neighbor_positions(&Coord::new(0, 0), Neighborhood::Moore, Boundary::Wrap)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_count_neighbors() {
+        let grid = Grid::parse("#.#\n.##\n#..", |c| c == '#').unwrap();
+        let center = Coord::new(1, 1);
+        assert_eq!(
+            grid.count_neighbors(&center, Neighborhood::Moore, Boundary::Dead, |b| *b),
+            4
+        );
+        assert_eq!(
+            grid.count_neighbors(&center, Neighborhood::Orthogonal, Boundary::Dead, |b| *b),
+            1
+        );
+        assert_eq!(
+            grid.count_neighbors(
+                &Coord::new(0, 0),
+                Neighborhood::Moore,
+                Boundary::Wrap,
+                |b| *b
+            ),
+            4
+        );
+    }
+
+    #[test]
+    fn test_rectangle_operations() {
+        let mut grid = Grid::<bool>::new(5, 3);
+        grid.fill_in_rectangle(
+            PositionalRectangle::new(Coord::new(1, 0), Coord::new(3, 1)),
+            true,
+        );
+        assert_eq!(grid.count_on(), 6);
+
+        grid.toggle_in_rectangle(PositionalRectangle::new(Coord::new(0, 1), Coord::new(4, 2)));
+        assert_eq!(grid.count_on(), 3 + 2 + 5);
+        assert_eq!(grid.count_off(), 5);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::parse("#..\n.#.", |c| c == '#').unwrap();
+        assert_eq!(grid.to_string(), "#..\n.#.");
+        assert_eq!(grid.render(|b| if *b { 'X' } else { ' ' }), "X  \n X ");
+
+        let chars = Grid::parse("ab\ncd", |c| c).unwrap();
+        assert_eq!(chars.to_string(), "ab\ncd");
+    }
+
+    #[test]
+    fn test_parse_art() {
+        let grid = Grid::parse_art(
+            "
+            .#.
+            ###
+             #
+            ",
+            |c| c == '#',
+        );
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.to_string(), ".#.\n###\n.#.");
+    }
+
+    #[test]
+    fn test_crop() {
+        let grid = Grid::parse(".....\n..#..\n...#.\n.....", |c| c == '#').unwrap();
+        assert_eq!(
+            grid.bounding_box(|b| *b),
+            Some(PositionalRectangle::new(Coord::new(2, 1), Coord::new(3, 2)))
+        );
+        assert_eq!(grid.crop(|b| *b).to_string(), "#.\n.#");
+        assert_eq!(Grid::<bool>::new(2, 2).crop(|b| *b).width(), 0);
+    }
+
+    #[test]
+    fn test_images() {
+        let grid = Grid::parse("#.\n.#", |c| c == '#').unwrap();
+        assert_eq!(grid.to_pbm(), "P1\n2 2\n1 0\n0 1\n");
+
+        let ppm = grid.to_ppm(|b| if *b { [255, 255, 255] } else { [0, 0, 0] });
+        assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(ppm.len(), "P6\n2 2\n255\n".len() + 4 * 3);
     }
 }
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::ops::Index;
+
+/// Maps names to dense ids `0..len`, in the order the names are first seen,
+/// so that data keyed by name can be stored in vectors and bitsets instead of maps.
+#[derive(Clone, Debug, Default)]
+pub struct Interner<'a> {
+    _names: Vec<&'a str>,
+    _ids: HashMap<&'a str, usize>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `name`, a new id is assigned the first time a name is seen.
+    pub fn intern(&mut self, name: &'a str) -> usize {
+        if let Some(&id) = self._ids.get(name) {
+            return id;
+        }
+
+        let id = self._names.len();
+        self._names.push(name);
+        self._ids.insert(name, id);
+        id
+    }
+
+    /// The id of `name`, if it was interned.
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self._ids.get(name).copied()
+    }
+
+    /// The name with the given id, panics if the id was not handed out by this interner.
+    pub fn name(&self, id: usize) -> &'a str {
+        self._names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self._names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self._names.is_empty()
+    }
+
+    /// All names, indexed by id.
+    pub fn names(&self) -> &[&'a str] {
+        &self._names
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self._names.iter().copied().enumerate()
+    }
+}
+
+impl<'a> Index<usize> for Interner<'a> {
+    type Output = str;
+
+    fn index(&self, id: usize) -> &Self::Output {
+        self._names[id]
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Interner<'a> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(names: I) -> Self {
+        let mut interner = Interner::new();
+        for name in names {
+            interner.intern(name);
+        }
+        interner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+
+        assert_eq!(interner.intern("London"), 0);
+        assert_eq!(interner.intern("Dublin"), 1);
+        assert_eq!(interner.intern("London"), 0);
+
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("Dublin"), Some(1));
+        assert_eq!(interner.get("Belfast"), None);
+        assert_eq!(interner.name(1), "Dublin");
+        assert_eq!(&interner[0], "London");
+    }
+
+    #[test]
+    fn test_collect() {
+        let interner: Interner = "a b a c b".split(' ').collect();
+        assert_eq!(interner.names(), &["a", "b", "c"]);
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            vec![(0, "a"), (1, "b"), (2, "c")]
+        );
+    }
+}
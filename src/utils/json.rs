@@ -0,0 +1,964 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum JsonErrorKind {
+    UnexpectedByte(u8),
+    UnexpectedEnd,
+    InvalidEscape,
+    InvalidNumber,
+    /// A token that is valid JSON but not allowed at this position, e.g. a `:` in an array.
+    UnexpectedToken,
+}
+
+/// Invalid JSON, `position` is the byte offset where the problem was found.
+#[derive(Debug, PartialEq, Eq)]
+pub struct JsonError {
+    pub position: usize,
+    pub kind: JsonErrorKind,
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid JSON at byte {}: ", self.position)?;
+        match self.kind {
+            JsonErrorKind::UnexpectedByte(byte) => {
+                write!(f, "unexpected character '{}'.", byte.escape_ascii())
+            }
+            JsonErrorKind::UnexpectedEnd => write!(f, "unexpected end of input."),
+            JsonErrorKind::InvalidEscape => write!(f, "invalid escape sequence."),
+            JsonErrorKind::InvalidNumber => write!(f, "invalid number."),
+            JsonErrorKind::UnexpectedToken => write!(f, "unexpected token."),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// A string as it appears in the input, without quotes and with escape sequences not yet decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JsonStr<'a> {
+    _raw: &'a str,
+}
+
+/// The value of the four hex digits at the start of `s`, 0 if they are invalid.
+fn hex_code(s: &str) -> u32 {
+    s.get(..4)
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .unwrap_or_default()
+}
+
+impl<'a> JsonStr<'a> {
+    pub fn raw(&self) -> &'a str {
+        self._raw
+    }
+
+    /// The decoded string, only allocates if it contains escape sequences.
+    pub fn decode(&self) -> Cow<'a, str> {
+        if !self._raw.contains('\\') {
+            return Cow::Borrowed(self._raw);
+        }
+
+        let mut decoded = String::with_capacity(self._raw.len());
+        let mut chars = self._raw.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                decoded.push(c);
+                continue;
+            }
+
+            // escapes were validated by the tokenizer.
+            match chars.next() {
+                Some('b') => decoded.push('\u{8}'),
+                Some('f') => decoded.push('\u{c}'),
+                Some('n') => decoded.push('\n'),
+                Some('r') => decoded.push('\r'),
+                Some('t') => decoded.push('\t'),
+                Some('u') => {
+                    let mut code = hex_code(chars.as_str());
+                    chars.by_ref().take(4).for_each(drop);
+
+                    // a high surrogate combines with an escaped low surrogate after it.
+                    if (0xd800..0xdc00).contains(&code)
+                        && let Some(next) = chars.as_str().strip_prefix("\\u")
+                        && let low = hex_code(next)
+                        && (0xdc00..0xe000).contains(&low)
+                    {
+                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                        chars.by_ref().take(6).for_each(drop);
+                    }
+                    decoded.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                Some(other) => decoded.push(other),
+                None => {}
+            }
+        }
+
+        Cow::Owned(decoded)
+    }
+
+    /// Compares the decoded string with `other`, without allocating for strings without escapes.
+    #[inline]
+    pub fn is(&self, other: &str) -> bool {
+        if self._raw.contains('\\') {
+            self.decode() == other
+        } else {
+            self._raw == other
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token<'a> {
+    BeginObject,
+    EndObject,
+    BeginArray,
+    EndArray,
+    Colon,
+    Comma,
+    String(JsonStr<'a>),
+    /// The number as written, e.g. `-1.5e3`.
+    Number(&'a str),
+    Bool(bool),
+    Null,
+}
+
+/// Splits JSON into tokens without allocating. Only single tokens are validated,
+/// the structure is checked by the consumers.
+#[derive(Clone)]
+pub struct Tokenizer<'a> {
+    _input: &'a str,
+    _position: usize,
+    _token_start: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Tokenizer {
+            _input: input,
+            _position: 0,
+            _token_start: 0,
+        }
+    }
+
+    /// Starts tokenizing at the byte offset `position`, error positions stay relative to `input`.
+    pub fn at(input: &'a str, position: usize) -> Self {
+        Tokenizer {
+            _input: input,
+            _position: position,
+            _token_start: position,
+        }
+    }
+
+    /// Byte offset after the last token.
+    pub fn offset(&self) -> usize {
+        self._position
+    }
+
+    /// Byte offset of the first byte of the last token.
+    pub fn token_start(&self) -> usize {
+        self._token_start
+    }
+
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        let bytes = self._input.as_bytes();
+        while bytes
+            .get(self._position)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self._position += 1;
+        }
+    }
+
+    /// The next token, reaching the end of the input is an error.
+    #[inline]
+    fn read_token(&mut self) -> Result<Token<'a>, JsonError> {
+        self.skip_whitespace();
+        let Some(&byte) = self._input.as_bytes().get(self._position) else {
+            return Err(self.error(self._position, JsonErrorKind::UnexpectedEnd));
+        };
+
+        self._token_start = self._position;
+        self._position += 1;
+
+        match byte {
+            b'{' => Ok(Token::BeginObject),
+            b'}' => Ok(Token::EndObject),
+            b'[' => Ok(Token::BeginArray),
+            b']' => Ok(Token::EndArray),
+            b':' => Ok(Token::Colon),
+            b',' => Ok(Token::Comma),
+            b'"' => self.string(),
+            b'-' | b'0'..=b'9' => self.number(),
+            b't' => self.expect_literal("true", Token::Bool(true)),
+            b'f' => self.expect_literal("false", Token::Bool(false)),
+            b'n' => self.expect_literal("null", Token::Null),
+            byte => Err(self.error(self._token_start, JsonErrorKind::UnexpectedByte(byte))),
+        }
+    }
+
+    /// Consumes `byte` if it is the next non-whitespace byte.
+    #[inline]
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self._input.as_bytes().get(self._position) == Some(&byte) {
+            self._token_start = self._position;
+            self._position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn error(&self, position: usize, kind: JsonErrorKind) -> JsonError {
+        JsonError { position, kind }
+    }
+
+    fn expect_literal(&mut self, literal: &str, token: Token<'a>) -> Result<Token<'a>, JsonError> {
+        let start = self._token_start;
+        if self._input.as_bytes()[start..].starts_with(literal.as_bytes()) {
+            self._position = start + literal.len();
+            Ok(token)
+        } else {
+            Err(self.error(
+                start,
+                JsonErrorKind::UnexpectedByte(self._input.as_bytes()[start]),
+            ))
+        }
+    }
+
+    #[inline]
+    fn string(&mut self) -> Result<Token<'a>, JsonError> {
+        let bytes = self._input.as_bytes();
+        let start = self._token_start + 1;
+        let mut i = start;
+
+        loop {
+            // fast path: jump to the next quote or escape.
+            let offset = bytes[i..].iter().position(|&b| b == b'"' || b == b'\\');
+            let Some(offset) = offset else {
+                return Err(self.error(bytes.len(), JsonErrorKind::UnexpectedEnd));
+            };
+            i += offset;
+
+            if bytes[i] == b'"' {
+                break;
+            }
+
+            match bytes.get(i + 1) {
+                Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {}
+                Some(b'u')
+                    if bytes
+                        .get(i + 2..i + 6)
+                        .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) =>
+                {
+                    i += 4;
+                }
+                _ => return Err(self.error(i, JsonErrorKind::InvalidEscape)),
+            }
+            i += 2;
+        }
+
+        self._position = i + 1;
+        Ok(Token::String(JsonStr {
+            _raw: &self._input[start..i],
+        }))
+    }
+
+    #[inline]
+    fn number(&mut self) -> Result<Token<'a>, JsonError> {
+        let bytes = self._input.as_bytes();
+        let start = self._token_start;
+
+        let digits = |from: usize| {
+            bytes[from..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+        };
+
+        // -?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?
+        let mut end = start + usize::from(bytes[start] == b'-');
+        let integer = match bytes.get(end) {
+            Some(b'0') => 1,
+            Some(b'1'..=b'9') => digits(end),
+            _ => 0,
+        };
+        if integer == 0 {
+            return Err(self.error(start, JsonErrorKind::InvalidNumber));
+        }
+        end += integer;
+
+        if bytes.get(end) == Some(&b'.') {
+            let fraction = digits(end + 1);
+            if fraction == 0 {
+                return Err(self.error(end, JsonErrorKind::InvalidNumber));
+            }
+            end += 1 + fraction;
+        }
+
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let sign = usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+            let exponent = digits(end + 1 + sign);
+            if exponent == 0 {
+                return Err(self.error(end, JsonErrorKind::InvalidNumber));
+            }
+            end += 1 + sign + exponent;
+        }
+
+        // a number has to be followed by a delimiter, e.g. `1-2` or `01` are not numbers.
+        if matches!(
+            bytes.get(end),
+            Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+        ) {
+            return Err(self.error(end, JsonErrorKind::InvalidNumber));
+        }
+
+        self._position = end;
+        Ok(Token::Number(&self._input[start..end]))
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Token<'a>, JsonError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_whitespace();
+        if self._position == self._input.len() {
+            return None;
+        }
+        Some(self.read_token())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A lazily parsed JSON value. Arrays and objects only keep where they start in the input,
+/// their elements are parsed while iterating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value<'a> {
+    Null,
+    Bool(bool),
+    Number(&'a str),
+    String(JsonStr<'a>),
+    Array(Array<'a>),
+    Object(Object<'a>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Array<'a> {
+    _input: &'a str,
+    _start: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Object<'a> {
+    _input: &'a str,
+    _start: usize,
+}
+
+#[inline]
+fn unexpected_token(tokenizer: &Tokenizer<'_>) -> JsonError {
+    JsonError {
+        position: tokenizer.token_start(),
+        kind: JsonErrorKind::UnexpectedToken,
+    }
+}
+
+/// The value starting with `first`. For arrays and objects, the tokenizer stays after the bracket.
+#[inline]
+fn read_value<'a>(tokenizer: &Tokenizer<'a>, first: Token<'a>) -> Result<Value<'a>, JsonError> {
+    let (input, start) = (tokenizer._input, tokenizer.token_start());
+
+    match first {
+        Token::Null => Ok(Value::Null),
+        Token::Bool(b) => Ok(Value::Bool(b)),
+        Token::Number(n) => Ok(Value::Number(n)),
+        Token::String(s) => Ok(Value::String(s)),
+        Token::BeginArray => Ok(Value::Array(Array {
+            _input: input,
+            _start: start,
+        })),
+        Token::BeginObject => Ok(Value::Object(Object {
+            _input: input,
+            _start: start,
+        })),
+        _ => Err(unexpected_token(tokenizer)),
+    }
+}
+
+#[inline]
+fn is_container(value: &Value<'_>) -> bool {
+    matches!(value, Value::Array(_) | Value::Object(_))
+}
+
+/// Moves the tokenizer past `depth` closing brackets by counting brackets,
+/// which needs no stack and therefore works for any depth.
+/// Only strings are tokenized on the way, everything else is skipped unchecked.
+#[inline]
+fn skip_closing(tokenizer: &mut Tokenizer<'_>, mut depth: usize) -> Result<(), JsonError> {
+    let bytes = tokenizer._input.as_bytes();
+
+    while depth > 0 {
+        let start = tokenizer._position;
+        let offset = bytes[start..]
+            .iter()
+            .position(|b| matches!(b, b'[' | b']' | b'{' | b'}' | b'"'));
+        let Some(offset) = offset else {
+            return Err(JsonError {
+                position: bytes.len(),
+                kind: JsonErrorKind::UnexpectedEnd,
+            });
+        };
+
+        tokenizer._token_start = start + offset;
+        tokenizer._position = tokenizer._token_start + 1;
+        match bytes[tokenizer._token_start] {
+            b'"' => {
+                tokenizer.string()?;
+            }
+            b'[' | b'{' => depth += 1,
+            _ => depth -= 1,
+        }
+    }
+    Ok(())
+}
+
+/// Moves the tokenizer past the rest of `value`.
+fn skip_rest(tokenizer: &mut Tokenizer<'_>, value: &Value<'_>) -> Result<(), JsonError> {
+    skip_closing(tokenizer, usize::from(is_container(value)))
+}
+
+#[inline]
+fn expect_end(tokenizer: &mut Tokenizer<'_>) -> Result<(), JsonError> {
+    match tokenizer.next() {
+        None => Ok(()),
+        Some(Err(e)) => Err(e),
+        Some(Ok(_)) => Err(unexpected_token(tokenizer)),
+    }
+}
+
+/// Reads the separators and keys between the elements of an array or object.
+struct Elements {
+    _object: bool,
+    _first: bool,
+}
+
+impl Elements {
+    #[inline]
+    fn new(object: bool) -> Self {
+        Elements {
+            _object: object,
+            _first: true,
+        }
+    }
+
+    /// The key and the first token of the next element, `None` after the closing bracket.
+    #[inline]
+    fn next_start<'a>(
+        &mut self,
+        tokenizer: &mut Tokenizer<'a>,
+    ) -> Result<Option<(Option<JsonStr<'a>>, Token<'a>)>, JsonError> {
+        // separators are checked on the bytes directly, which saves a full token dispatch.
+        // a closing bracket after a comma is read as element and rejected by `read_value`.
+        let token = if !self._first && tokenizer.eat(b',') {
+            tokenizer.read_token()?
+        } else {
+            let token = tokenizer.read_token()?;
+            let end = if self._object {
+                Token::EndObject
+            } else {
+                Token::EndArray
+            };
+            if token == end {
+                return Ok(None);
+            }
+            if !self._first {
+                return Err(unexpected_token(tokenizer));
+            }
+            token
+        };
+        self._first = false;
+
+        if !self._object {
+            return Ok(Some((None, token)));
+        }
+
+        let Token::String(key) = token else {
+            return Err(unexpected_token(tokenizer));
+        };
+        if !tokenizer.eat(b':') {
+            // read whatever is there instead, for the position of the error.
+            tokenizer.read_token()?;
+            return Err(unexpected_token(tokenizer));
+        }
+        Ok(Some((Some(key), tokenizer.read_token()?)))
+    }
+}
+
+impl<'a> Value<'a> {
+    /// Parses a document consisting of a single value.
+    pub fn parse(input: &'a str) -> Result<Self, JsonError> {
+        let mut tokenizer = Tokenizer::new(input);
+        let first = tokenizer.read_token()?;
+        let value = read_value(&tokenizer, first)?;
+        skip_rest(&mut tokenizer, &value)?;
+        expect_end(&mut tokenizer)?;
+        Ok(value)
+    }
+
+    /// The number as integer, `None` for fractions, exponents and numbers out of range.
+    #[inline]
+    pub fn as_i64(&self) -> Option<i64> {
+        let Value::Number(n) = self else {
+            return None;
+        };
+
+        // the tokenizer validated the number, so it is an integer if it has only digits.
+        let (negative, digits) = match n.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, *n),
+        };
+        digits.bytes().try_fold(0i64, |value, digit| {
+            if !digit.is_ascii_digit() {
+                return None;
+            }
+            let digit = i64::from(digit - b'0');
+            let value = value.checked_mul(10)?;
+            if negative {
+                value.checked_sub(digit)
+            } else {
+                value.checked_add(digit)
+            }
+        })
+    }
+
+    #[inline]
+    pub fn is_str(&self, other: &str) -> bool {
+        matches!(self, Value::String(s) if s.is(other))
+    }
+
+    /// Visits this value and all nested values depth-first, parents before their children.
+    /// Values for which `prune` returns `true` are skipped together with their children.
+    /// Nested values are not allocated, only a stack with one entry per nesting level.
+    pub fn walk(
+        &self,
+        mut prune: impl FnMut(&Value<'a>) -> bool,
+        mut visit: impl FnMut(&Value<'a>),
+    ) -> Result<(), JsonError> {
+        let (input, start) = match self {
+            Value::Array(array) => (array._input, array._start),
+            Value::Object(object) => (object._input, object._start),
+            _ => {
+                if !prune(self) {
+                    visit(self);
+                }
+                return Ok(());
+            }
+        };
+
+        // children are read with the same tokenizer as their parent, nothing is read twice.
+        let mut tokenizer = Tokenizer::at(input, start);
+        let first = tokenizer.read_token()?;
+        let mut stack: Vec<Elements> = vec![];
+        let mut next = Some(read_value(&tokenizer, first)?);
+
+        loop {
+            if let Some(value) = next.take() {
+                if prune(&value) {
+                    skip_rest(&mut tokenizer, &value)?;
+                } else {
+                    visit(&value);
+                    match value {
+                        Value::Array(_) => stack.push(Elements::new(false)),
+                        Value::Object(_) => stack.push(Elements::new(true)),
+                        _ => {}
+                    }
+                }
+            }
+
+            let Some(elements) = stack.last_mut() else {
+                return Ok(());
+            };
+
+            match elements.next_start(&mut tokenizer)? {
+                Some((_, first)) => next = Some(read_value(&tokenizer, first)?),
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Folds all scalars of a JSON document into one value in a single pass, without building it.
+/// The only allocation is a stack with one entry per nesting level.
+/// `leaf` maps each scalar and `combine` merges the results, starting from `T::default()`
+/// for every array or object.
+///
+/// `prune` is called for every element of an array or object with its key, if any.
+/// If it returns `true`, the container holding the element is discarded together with
+/// everything inside it, as if it were empty.
+pub fn fold<'a, T: Default>(
+    input: &'a str,
+    mut prune: impl FnMut(Option<JsonStr<'a>>, &Value<'a>) -> bool,
+    mut leaf: impl FnMut(&Value<'a>) -> T,
+    mut combine: impl FnMut(T, T) -> T,
+) -> Result<T, JsonError> {
+    let mut tokenizer = Tokenizer::new(input);
+    let first = tokenizer.read_token()?;
+    let root = read_value(&tokenizer, first)?;
+
+    let mut result = T::default();
+    let mut stack: Vec<(Elements, T)> = vec![];
+    match root {
+        Value::Array(_) => stack.push((Elements::new(false), T::default())),
+        Value::Object(_) => stack.push((Elements::new(true), T::default())),
+        scalar => result = leaf(&scalar),
+    }
+
+    while let Some((elements, _)) = stack.last_mut() {
+        let Some((key, first)) = elements.next_start(&mut tokenizer)? else {
+            let (_, finished) = stack.pop().expect("stack is not empty");
+            match stack.last_mut() {
+                Some((_, parent)) => *parent = combine(std::mem::take(parent), finished),
+                None => result = finished,
+            }
+            continue;
+        };
+
+        let value = read_value(&tokenizer, first)?;
+        if prune(key, &value) {
+            // the rest of the element and the rest of its container.
+            skip_closing(&mut tokenizer, 1 + usize::from(is_container(&value)))?;
+            stack.pop();
+            continue;
+        }
+
+        match value {
+            Value::Array(_) => stack.push((Elements::new(false), T::default())),
+            Value::Object(_) => stack.push((Elements::new(true), T::default())),
+            scalar => {
+                let (_, acc) = stack.last_mut().expect("stack is not empty");
+                *acc = combine(std::mem::take(acc), leaf(&scalar));
+            }
+        }
+    }
+
+    expect_end(&mut tokenizer)?;
+    Ok(result)
+}
+
+pub struct ArrayIter<'a> {
+    _tokenizer: Tokenizer<'a>,
+    _elements: Elements,
+    _done: bool,
+}
+
+impl<'a> Array<'a> {
+    pub fn iter(&self) -> ArrayIter<'a> {
+        let mut tokenizer = Tokenizer::at(self._input, self._start);
+        // skip `[`, which was validated when the array was read.
+        tokenizer.next();
+        ArrayIter {
+            _tokenizer: tokenizer,
+            _elements: Elements::new(false),
+            _done: false,
+        }
+    }
+}
+
+impl<'a> ArrayIter<'a> {
+    fn read_next(&mut self) -> Result<Option<Value<'a>>, JsonError> {
+        let tokenizer = &mut self._tokenizer;
+        let Some((_, first)) = self._elements.next_start(tokenizer)? else {
+            return Ok(None);
+        };
+
+        let value = read_value(tokenizer, first)?;
+        skip_rest(tokenizer, &value)?;
+        Ok(Some(value))
+    }
+}
+
+impl<'a> Iterator for ArrayIter<'a> {
+    type Item = Result<Value<'a>, JsonError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self._done {
+            return None;
+        }
+
+        let next = self.read_next();
+        self._done = !matches!(next, Ok(Some(_)));
+        next.transpose()
+    }
+}
+
+pub struct ObjectIter<'a> {
+    _tokenizer: Tokenizer<'a>,
+    _elements: Elements,
+    _done: bool,
+}
+
+impl<'a> Object<'a> {
+    pub fn iter(&self) -> ObjectIter<'a> {
+        let mut tokenizer = Tokenizer::at(self._input, self._start);
+        // skip `{`, which was validated when the object was read.
+        tokenizer.next();
+        ObjectIter {
+            _tokenizer: tokenizer,
+            _elements: Elements::new(true),
+            _done: false,
+        }
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = Result<Value<'a>, JsonError>> {
+        self.iter().map(|entry| entry.map(|(_, value)| value))
+    }
+
+    /// The value of the first entry with the given key.
+    pub fn get(&self, key: &str) -> Result<Option<Value<'a>>, JsonError> {
+        for entry in self.iter() {
+            let (k, value) = entry?;
+            if k.is(key) {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<'a> ObjectIter<'a> {
+    fn read_next(&mut self) -> Result<Option<(JsonStr<'a>, Value<'a>)>, JsonError> {
+        let tokenizer = &mut self._tokenizer;
+        let Some((Some(key), first)) = self._elements.next_start(tokenizer)? else {
+            return Ok(None);
+        };
+
+        let value = read_value(tokenizer, first)?;
+        skip_rest(tokenizer, &value)?;
+        Ok(Some((key, value)))
+    }
+}
+
+impl<'a> Iterator for ObjectIter<'a> {
+    type Item = Result<(JsonStr<'a>, Value<'a>), JsonError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self._done {
+            return None;
+        }
+
+        let next = self.read_next();
+        self._done = !matches!(next, Ok(Some(_)));
+        next.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum_numbers(input: &str, prune: impl FnMut(&Value) -> bool) -> i64 {
+        let mut sum = 0;
+        Value::parse(input)
+            .unwrap()
+            .walk(prune, |v| sum += v.as_i64().unwrap_or(0))
+            .unwrap();
+        sum
+    }
+
+    fn has_red_value(value: &Value) -> bool {
+        match value {
+            Value::Object(object) => object.values().any(|v| v.unwrap().is_str("red")),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_tokenizer() {
+        let tokens: Vec<Token> = Tokenizer::new(r#" {"a\"b": [-1.5e3, true, null]} "#)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::BeginObject,
+                Token::String(JsonStr { _raw: r#"a\"b"# }),
+                Token::Colon,
+                Token::BeginArray,
+                Token::Number("-1.5e3"),
+                Token::Comma,
+                Token::Bool(true),
+                Token::Comma,
+                Token::Null,
+                Token::EndArray,
+                Token::EndObject,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strings() {
+        let value = Value::parse(r#""tab\t quote\" unicodeé slash\/""#).unwrap();
+        let Value::String(s) = value else {
+            panic!("expected a string");
+        };
+        assert_eq!(s.decode(), "tab\t quote\" unicode\u{e9} slash/");
+        assert!(matches!(s.decode(), Cow::Owned(_)));
+
+        let s = JsonStr { _raw: "red" };
+        assert!(matches!(s.decode(), Cow::Borrowed("red")));
+        assert!(s.is("red"));
+    }
+
+    #[test]
+    fn test_iteration() {
+        let value = Value::parse(r#"{"a": [1, {"b": 2}], "c": "d", "e": {}}"#).unwrap();
+        let Value::Object(object) = value else {
+            panic!("expected an object");
+        };
+
+        let keys: Vec<_> = object.iter().map(|e| e.unwrap().0.raw()).collect();
+        assert_eq!(keys, vec!["a", "c", "e"]);
+        assert!(object.get("c").unwrap().unwrap().is_str("d"));
+        assert_eq!(object.get("x"), Ok(None));
+
+        let Some(Value::Array(array)) = object.get("a").unwrap() else {
+            panic!("expected an array");
+        };
+        assert_eq!(array.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_walk() {
+        assert_eq!(sum_numbers("[1,2,3]", |_| false), 6);
+        assert_eq!(sum_numbers(r#"{"a":{"b":4},"c":-1}"#, |_| false), 3);
+        assert_eq!(sum_numbers(r#"[1,{"c":"red","b":2},3]"#, has_red_value), 4);
+        assert_eq!(
+            sum_numbers(r#"{"d":"red","e":[1,2,3,4],"f":5}"#, has_red_value),
+            0
+        );
+        assert_eq!(sum_numbers(r#"[1,"red",5]"#, has_red_value), 6);
+    }
+
+    #[test]
+    fn test_fold() {
+        let sum = |input: &str| {
+            fold(
+                input,
+                |key, value| key.is_some() && value.is_str("red"),
+                |value| value.as_i64().unwrap_or(0),
+                |a, b| a + b,
+            )
+        };
+
+        assert_eq!(sum("[1,2,3]"), Ok(6));
+        assert_eq!(sum(r#"[1,{"c":"red","b":2},3]"#), Ok(4));
+        assert_eq!(sum(r#"{"d":"red","e":[1,2,3,4],"f":5}"#), Ok(0));
+        assert_eq!(sum(r#"[1,{"a":[5,{"b":6}],"c":"red"},"red",5]"#), Ok(6));
+        assert_eq!(sum("42"), Ok(42));
+        assert_eq!(
+            sum(r#"[{"a":"red", "b":[1}]"#).unwrap_err().kind,
+            JsonErrorKind::UnexpectedEnd
+        );
+        assert_eq!(
+            sum("[1] 2").unwrap_err().kind,
+            JsonErrorKind::UnexpectedToken
+        );
+
+        let depth = 100_000;
+        let input = format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(sum(&input), Ok(1));
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let depth = 100_000;
+        let input = format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+        assert_eq!(sum_numbers(&input, |_| false), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |input: &str| {
+            let mut result = Value::parse(input).map(|_| ());
+            if let Ok(value) = Value::parse(input) {
+                result = value.walk(|_| false, |_| {});
+            }
+            result.unwrap_err()
+        };
+
+        assert_eq!(
+            error("[1, 2"),
+            JsonError {
+                position: 5,
+                kind: JsonErrorKind::UnexpectedEnd
+            }
+        );
+        assert_eq!(
+            error("[[1 2]]"),
+            JsonError {
+                position: 4,
+                kind: JsonErrorKind::UnexpectedToken
+            }
+        );
+        assert_eq!(error(r#"{"a" 1}"#).kind, JsonErrorKind::UnexpectedToken);
+        assert_eq!(error(r#""\x""#).kind, JsonErrorKind::InvalidEscape);
+        assert_eq!(error("[tru]").kind, JsonErrorKind::UnexpectedByte(b't'));
+        assert_eq!(error("[1,]").kind, JsonErrorKind::UnexpectedToken);
+        assert_eq!(error("1 2").kind, JsonErrorKind::UnexpectedToken);
+        assert_eq!(error("@").kind, JsonErrorKind::UnexpectedByte(b'@'));
+
+        for number in ["1-2", "1..2", "1e", "1.", "-", "01", "1e+", "1.5.2", "-.5"] {
+            assert_eq!(error(number).kind, JsonErrorKind::InvalidNumber, "{number}");
+        }
+        assert_eq!(
+            error("[1, 2-3]"),
+            JsonError {
+                position: 5,
+                kind: JsonErrorKind::InvalidNumber
+            }
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        for number in ["0", "-0", "12", "-1.5", "0.25", "1e3", "2E-2", "-3.5e+10"] {
+            let tokens: Vec<Token> = Tokenizer::new(number).collect::<Result<_, _>>().unwrap();
+            assert_eq!(tokens, vec![Token::Number(number)]);
+        }
+    }
+
+    #[test]
+    fn test_as_i64() {
+        let number = |n| Value::Number(n).as_i64();
+        assert_eq!(number("0"), Some(0));
+        assert_eq!(number("-42"), Some(-42));
+        assert_eq!(number("9223372036854775807"), Some(i64::MAX));
+        assert_eq!(number("-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(number("9223372036854775808"), None);
+        assert_eq!(number("1.5"), None);
+        assert_eq!(number("1e3"), None);
+        assert_eq!(Value::Null.as_i64(), None);
+    }
+
+    #[test]
+    fn test_surrogate_pairs() {
+        let decode = |input: &str| match Tokenizer::new(input).next() {
+            Some(Ok(Token::String(s))) => s.decode().into_owned(),
+            other => panic!("expected a string, got {other:?}"),
+        };
+
+        assert_eq!(decode(r#""\uD83D\uDE00""#), "\u{1F600}");
+        assert_eq!(decode(r#""a\ud83d\ude00b\u00e9""#), "a\u{1F600}b\u{e9}");
+        // lone surrogates can't be decoded.
+        assert_eq!(decode(r#""\uD83D!""#), "\u{FFFD}!");
+        assert_eq!(decode(r#""\uDE00\uD83D""#), "\u{FFFD}\u{FFFD}");
+    }
+}
@@ -1,4 +1,17 @@
+pub mod automaton;
+pub mod circuit;
+pub mod combinatorics;
 pub mod coord_2d;
+pub mod escape;
 pub mod geom;
+pub mod graph;
 pub mod grid;
+pub mod intern;
+pub mod json;
+pub mod ocr;
 pub mod parsing;
+pub mod point;
+pub mod regions;
+pub mod scanner;
+pub mod search;
+pub mod sparse_grid;
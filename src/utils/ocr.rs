@@ -0,0 +1,254 @@
+use std::fmt::Display;
+
+use crate::utils::coord_2d::Coord;
+use crate::utils::geom::PositionalRectangle;
+use crate::utils::grid::Grid;
+use crate::utils::sparse_grid::SparseGrid;
+
+/// Letters of the 4×6 font, as drawn in e.g. 2016 day 8 or 2019 day 11.
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters of the 6×10 font, as drawn in e.g. 2018 day 10.
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// No cell is lit.
+    Empty,
+    /// The lit cells are neither 6 nor 10 rows high.
+    UnsupportedHeight(usize),
+    /// A glyph that is not part of the font, drawn with `#` and `.`.
+    UnknownGlyph { position: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "no letters found, the grid is empty."),
+            OcrError::UnsupportedHeight(height) => write!(
+                f,
+                "letters are {height} cells high, only fonts with a height of 6 or 10 are known."
+            ),
+            OcrError::UnknownGlyph { position, glyph } => {
+                write!(f, "glyph {position} is not a known letter:\n{glyph}")
+            }
+        }
+    }
+}
+
+/// Splits a cropped grid into glyphs, which are separated by at least one blank column.
+fn split_glyphs(grid: &Grid<bool>) -> Vec<Grid<bool>> {
+    let column_is_blank =
+        |x: usize| (0..grid.height()).all(|y| !grid[Coord::new(x as i64, y as i64)]);
+
+    let mut glyphs = vec![];
+    let mut start = None;
+
+    for x in 0..=grid.width() {
+        let blank = x == grid.width() || column_is_blank(x);
+        match (start, blank) {
+            (None, false) => start = Some(x),
+            (Some(begin), true) => {
+                let rect = PositionalRectangle::new(
+                    Coord::new(begin as i64, 0),
+                    Coord::new(x as i64 - 1, grid.height() as i64 - 1),
+                );
+                glyphs.push(grid.sub_grid(rect));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    glyphs
+}
+
+/// Reads the letters drawn by the lit cells of `grid`.
+/// The font is picked by the height of the lit area, surrounding blank cells are ignored.
+pub fn recognize(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let cropped = grid.crop(|b| *b);
+
+    let font: &[(char, &str)] = match cropped.height() {
+        0 => return Err(OcrError::Empty),
+        6 => &SMALL_FONT,
+        10 => &LARGE_FONT,
+        height => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    split_glyphs(&cropped)
+        .iter()
+        .enumerate()
+        .map(|(position, glyph)| {
+            let glyph = glyph.to_string();
+            font.iter()
+                .find(|(_, letter)| *letter == glyph)
+                .map(|(c, _)| *c)
+                .ok_or(OcrError::UnknownGlyph { position, glyph })
+        })
+        .collect()
+}
+
+/// Like [`recognize`], every set cell counts as lit.
+pub fn recognize_sparse<T>(grid: &SparseGrid<T>) -> Result<String, OcrError> {
+    let art = grid.render(|cell| if cell.is_some() { '#' } else { '.' });
+    let dense = Grid::parse(&art, |c| c == '#').map_err(|_| OcrError::Empty)?;
+    recognize(&dense)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `text` in the given font with `spacing` blank columns between letters.
+    fn draw(font: &[(char, &str)], text: &str, spacing: usize) -> Grid<bool> {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| {
+                let (_, glyph) = font.iter().find(|(letter, _)| *letter == c).unwrap();
+                glyph.lines().collect()
+            })
+            .collect();
+        let height = glyphs[0].len();
+
+        let art = (0..height)
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| g[y].to_string())
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(spacing))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Grid::parse(&art, |c| c == '#').unwrap()
+    }
+
+    #[test]
+    fn test_small_font() {
+        let grid = draw(&SMALL_FONT, "ABCEFGHIJKLOPRSUYZ", 1);
+        assert_eq!(recognize(&grid), Ok("ABCEFGHIJKLOPRSUYZ".to_string()));
+    }
+
+    #[test]
+    fn test_large_font() {
+        let grid = draw(&LARGE_FONT, "ABCEFGHJKLNPRXZ", 2);
+        assert_eq!(recognize(&grid), Ok("ABCEFGHJKLNPRXZ".to_string()));
+    }
+
+    #[test]
+    fn test_padding_is_ignored() {
+        let letters = draw(&SMALL_FONT, "HI", 1);
+        let mut grid = Grid::<bool>::new(letters.width() + 4, letters.height() + 3);
+        for cell in letters.iter().filter(|it| it.value) {
+            grid[cell.position + Coord::new(2, 1)] = true;
+        }
+        assert_eq!(recognize(&grid), Ok("HI".to_string()));
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let letters = draw(&SMALL_FONT, "LO", 1);
+        let mut grid = SparseGrid::new();
+        for cell in letters.iter().filter(|it| it.value) {
+            grid.insert(cell.position - Coord::new(10, 20), ());
+        }
+        assert_eq!(recognize_sparse(&grid), Ok("LO".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(recognize(&Grid::new(5, 6)), Err(OcrError::Empty));
+        assert_eq!(
+            recognize(&Grid::filled(3, 4, true)),
+            Err(OcrError::UnsupportedHeight(4))
+        );
+        assert_eq!(
+            recognize(&Grid::filled(3, 6, true)),
+            Err(OcrError::UnknownGlyph {
+                position: 0,
+                glyph: "###\n###\n###\n###\n###\n###".to_string()
+            })
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use std::str::FromStr;
+use std::fmt::Display;
+
+use crate::utils::scanner::{ScanInt, scan_ints};
+use std::str::{FromStr, SplitAsciiWhitespace};
 
 pub trait UnwrapNextInt {
     fn unwrap_next_int<T: FromStr>(&mut self) -> T
@@ -15,9 +18,114 @@ impl<'a, U: Iterator<Item = &'a str>> UnwrapNextInt for U {
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingToken,
+    InvalidInt(String),
+    UnexpectedWord(String),
+}
+
+/// A token of a line that could not be parsed, `position` counts tokens from 0.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: String,
+    pub position: usize,
+    pub token: Option<String>,
+    pub kind: ParseErrorKind,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "token {} of \"{}\": ", self.position, self.line)?;
+        let token = self.token.as_deref().unwrap_or_default();
+        match &self.kind {
+            ParseErrorKind::MissingToken => write!(f, "expected another token but the line ended."),
+            ParseErrorKind::InvalidInt(reason) => {
+                write!(f, "expected an integer but found \"{token}\" ({reason}).")
+            }
+            ParseErrorKind::UnexpectedWord(expected) => {
+                write!(f, "expected \"{expected}\" but found \"{token}\".")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Whitespace separated tokens of a line, with fallible accessors that report where parsing failed.
+pub struct Tokens<'a> {
+    _line: &'a str,
+    _split: SplitAsciiWhitespace<'a>,
+    _position: usize,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Tokens {
+            _line: line,
+            _split: line.split_ascii_whitespace(),
+            _position: 0,
+        }
+    }
+
+    fn error(&self, token: Option<&str>, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self._line.to_string(),
+            position: self._position - usize::from(token.is_some()),
+            token: token.map(str::to_string),
+            kind,
+        }
+    }
+
+    pub fn next_word(&mut self) -> Result<&'a str, ParseError> {
+        self.next()
+            .ok_or_else(|| self.error(None, ParseErrorKind::MissingToken))
+    }
+
+    pub fn next_int<T: FromStr>(&mut self) -> Result<T, ParseError>
+    where
+        <T as FromStr>::Err: Display,
+    {
+        let token = self.next_word()?;
+        token
+            .parse::<T>()
+            .map_err(|e| self.error(Some(token), ParseErrorKind::InvalidInt(e.to_string())))
+    }
+
+    /// Consumes the next token, which has to be `word`.
+    pub fn expect_word(&mut self, word: &str) -> Result<(), ParseError> {
+        let token = self.next_word()?;
+        if token == word {
+            Ok(())
+        } else {
+            Err(self.error(
+                Some(token),
+                ParseErrorKind::UnexpectedWord(word.to_string()),
+            ))
+        }
+    }
+
+    /// Consumes one token for every whitespace separated word of `words`.
+    pub fn expect_words(&mut self, words: &str) -> Result<(), ParseError> {
+        words
+            .split_ascii_whitespace()
+            .try_for_each(|word| self.expect_word(word))
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self._split.next()?;
+        self._position += 1;
+        Some(token)
+    }
+}
+
 fn part_of_digit(byte: u8, curr_idx: usize, slice: &[u8]) -> bool {
     let is_negative_sign =
-        curr_idx < slice.len() && (byte == b'-' && slice[curr_idx + 1].is_ascii_digit());
+        byte == b'-' && slice.get(curr_idx + 1).is_some_and(|b| b.is_ascii_digit());
 
     is_negative_sign || byte.is_ascii_digit()
 }
@@ -60,18 +168,14 @@ fn split_next_int<'a>(slice: &mut &'a str) -> Option<(&'a str, &'a str, bool)> {
 }
 
 pub trait IterInts {
-    fn iter_ints<T: FromStr>(&self) -> impl Iterator<Item = T> + '_;
+    fn iter_ints<T: ScanInt>(&self) -> impl Iterator<Item = T> + '_;
 
     fn iter_named_ints<T: FromStr>(&self) -> impl Iterator<Item = (&str, T)> + '_;
 }
 
 impl IterInts for &str {
-    fn iter_ints<T: FromStr>(&self) -> impl Iterator<Item = T> + '_ {
-        let mut slice = *self;
-        std::iter::from_fn(move || {
-            let (_, int_str, _) = split_next_int(&mut slice)?;
-            int_str.parse::<T>().ok()
-        })
+    fn iter_ints<T: ScanInt>(&self) -> impl Iterator<Item = T> + '_ {
+        scan_ints(self.as_bytes())
     }
 
     fn iter_named_ints<T: FromStr>(&self) -> impl Iterator<Item = (&str, T)> + '_ {
@@ -109,6 +213,433 @@ impl IterInts for &str {
         })
     }
 }
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Capture,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PatternErrorKind {
+    /// The literal text of the pattern was not found at byte `at` of the line.
+    MissingLiteral { literal: String, at: usize },
+    /// The line continues after the pattern ended.
+    TrailingText(String),
+    /// The pattern has a different number of captures than requested.
+    CaptureCount { expected: usize, found: usize },
+    InvalidCapture {
+        index: usize,
+        capture: String,
+        reason: String,
+    },
+}
+
+/// A line that did not match a [`Pattern`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct PatternError {
+    pub pattern: String,
+    pub line: String,
+    pub kind: PatternErrorKind,
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" does not match \"{}\": ", self.line, self.pattern)?;
+        match &self.kind {
+            PatternErrorKind::MissingLiteral { literal, at } => {
+                write!(f, "expected \"{literal}\" at byte {at}.")
+            }
+            PatternErrorKind::TrailingText(text) => write!(f, "unexpected trailing \"{text}\"."),
+            PatternErrorKind::CaptureCount { expected, found } => {
+                write!(
+                    f,
+                    "expected {expected} captures but the pattern has {found}."
+                )
+            }
+            PatternErrorKind::InvalidCapture {
+                index,
+                capture,
+                reason,
+            } => write!(f, "capture {index} \"{capture}\" is invalid ({reason})."),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// A line format with `{}` placeholders, e.g. `"{} to {} = {}"`.
+/// A capture extends up to the first occurrence of the literal text following it,
+/// or to the end of the line if it is the last part of the pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    _source: String,
+    _segments: Vec<Segment>,
+}
+
+impl Pattern {
+    /// Compiles a pattern. Panics if two captures follow each other, as they could not be told apart.
+    pub fn new(pattern: &str) -> Self {
+        let mut segments = vec![];
+
+        for (i, literal) in pattern.split("{}").enumerate() {
+            if i > 0 {
+                assert!(
+                    segments.last() != Some(&Segment::Capture),
+                    "invalid pattern \"{pattern}\", captures need to be separated by text."
+                );
+                segments.push(Segment::Capture);
+            }
+            if !literal.is_empty() {
+                segments.push(Segment::Literal(literal.to_string()));
+            }
+        }
+
+        Pattern {
+            _source: pattern.to_string(),
+            _segments: segments,
+        }
+    }
+
+    pub fn capture_count(&self) -> usize {
+        self._segments
+            .iter()
+            .filter(|s| **s == Segment::Capture)
+            .count()
+    }
+
+    fn error(&self, line: &str, kind: PatternErrorKind) -> PatternError {
+        PatternError {
+            pattern: self._source.clone(),
+            line: line.to_string(),
+            kind,
+        }
+    }
+
+    /// The text of every capture in `line`.
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Vec<&'a str>, PatternError> {
+        let mut captures = Vec::with_capacity(self._segments.len());
+        let mut position = 0;
+
+        for (i, segment) in self._segments.iter().enumerate() {
+            let rest = &line[position..];
+            match segment {
+                Segment::Literal(literal) => {
+                    if !rest.starts_with(literal.as_str()) {
+                        return Err(self.error(
+                            line,
+                            PatternErrorKind::MissingLiteral {
+                                literal: literal.clone(),
+                                at: position,
+                            },
+                        ));
+                    }
+                    position += literal.len();
+                }
+                Segment::Capture => {
+                    let len = match self._segments.get(i + 1) {
+                        Some(Segment::Literal(next)) => {
+                            rest.find(next.as_str()).ok_or_else(|| {
+                                self.error(
+                                    line,
+                                    PatternErrorKind::MissingLiteral {
+                                        literal: next.clone(),
+                                        at: position,
+                                    },
+                                )
+                            })?
+                        }
+                        _ => rest.len(),
+                    };
+                    captures.push(&rest[..len]);
+                    position += len;
+                }
+            }
+        }
+
+        if position < line.len() {
+            return Err(self.error(
+                line,
+                PatternErrorKind::TrailingText(line[position..].to_string()),
+            ));
+        }
+
+        Ok(captures)
+    }
+
+    /// Matches `line` and converts the captures into a tuple, e.g. `(&str, &str, u64)`.
+    pub fn parse<'a, T: FromCaptures<'a>>(&self, line: &'a str) -> Result<T, PatternError> {
+        let captures = self.captures(line)?;
+
+        if captures.len() != T::COUNT {
+            return Err(self.error(
+                line,
+                PatternErrorKind::CaptureCount {
+                    expected: T::COUNT,
+                    found: captures.len(),
+                },
+            ));
+        }
+
+        T::from_captures(&captures).map_err(|(index, reason)| {
+            self.error(
+                line,
+                PatternErrorKind::InvalidCapture {
+                    index,
+                    capture: captures[index].to_string(),
+                    reason,
+                },
+            )
+        })
+    }
+}
+
+/// Types a single capture of a [`Pattern`] can be converted to.
+pub trait FromCapture<'a>: Sized {
+    fn from_capture(capture: &'a str) -> Result<Self, String>;
+}
+
+impl<'a> FromCapture<'a> for &'a str {
+    fn from_capture(capture: &'a str) -> Result<Self, String> {
+        Ok(capture)
+    }
+}
+
+macro_rules! impl_from_capture {
+    ($($t:ty),*) => {$(
+        impl FromCapture<'_> for $t {
+            fn from_capture(capture: &str) -> Result<Self, String> {
+                capture.parse().map_err(|e: <$t as FromStr>::Err| e.to_string())
+            }
+        }
+    )*};
+}
+
+impl_from_capture!(
+    String, char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+/// Tuples of [`FromCapture`] types, converted from all captures of a line.
+/// Errors carry the index of the capture that could not be converted.
+pub trait FromCaptures<'a>: Sized {
+    const COUNT: usize;
+
+    fn from_captures(captures: &[&'a str]) -> Result<Self, (usize, String)>;
+}
+
+macro_rules! impl_from_captures {
+    ($count:expr; $($t:ident $i:tt),*) => {
+        impl<'a, $($t: FromCapture<'a>),*> FromCaptures<'a> for ($($t,)*) {
+            const COUNT: usize = $count;
+
+            fn from_captures(captures: &[&'a str]) -> Result<Self, (usize, String)> {
+                Ok(($($t::from_capture(captures[$i]).map_err(|e| ($i, e))?,)*))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1; A 0);
+impl_from_captures!(2; A 0, B 1);
+impl_from_captures!(3; A 0, B 1, C 2);
+impl_from_captures!(4; A 0, B 1, C 2, D 3);
+impl_from_captures!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_captures!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_captures!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_captures!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// Matches a line against a pattern with `{}` placeholders and converts the captures to the
+/// inferred tuple type, e.g.
+/// `let (from, to, distance): (&str, &str, u64) = parse_pattern!("{} to {} = {}", line)?;`
+#[macro_export]
+macro_rules! parse_pattern {
+    ($pattern:expr, $line:expr) => {
+        $crate::utils::parsing::Pattern::new($pattern).parse($line)
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The value of a record field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value<'a> {
+    Int(i64),
+    Bool(bool),
+    Word(&'a str),
+}
+
+impl<'a> Value<'a> {
+    fn parse(text: &'a str) -> Self {
+        match text {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => text.parse().map_or(Value::Word(text), Value::Int),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RecordErrorKind {
+    /// The line does not start with an id followed by `:`.
+    MissingId,
+    /// A field is neither `key: value` nor `key value`.
+    InvalidField(String),
+    MissingField(String),
+    WrongType {
+        key: String,
+        expected: &'static str,
+    },
+    /// A key that the target type does not know.
+    UnknownField(String),
+}
+
+/// A line that could not be parsed or deserialized as a [`Record`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct RecordError {
+    pub line: String,
+    pub kind: RecordErrorKind,
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "record \"{}\": ", self.line)?;
+        match &self.kind {
+            RecordErrorKind::MissingId => write!(f, "expected an id followed by \":\"."),
+            RecordErrorKind::InvalidField(field) => {
+                write!(f, "expected \"{field}\" to be a key and a value.")
+            }
+            RecordErrorKind::MissingField(key) => write!(f, "field \"{key}\" is missing."),
+            RecordErrorKind::WrongType { key, expected } => {
+                write!(f, "expected field \"{key}\" to be {expected}.")
+            }
+            RecordErrorKind::UnknownField(key) => write!(f, "unknown field \"{key}\"."),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+/// A line of comma separated `key: value` or `key value` fields, optionally preceded by an id,
+/// e.g. `Sue 12: cats: 7, trees: 3` or `Butterscotch: capacity -1, flavor 6`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub line: &'a str,
+    pub id: Option<&'a str>,
+    pub fields: Vec<(&'a str, Value<'a>)>,
+}
+
+impl<'a> Record<'a> {
+    /// Parses a record that starts with an id followed by `:`.
+    pub fn parse(line: &'a str) -> Result<Self, RecordError> {
+        let (id, fields) = line.split_once(':').ok_or_else(|| RecordError {
+            line: line.to_string(),
+            kind: RecordErrorKind::MissingId,
+        })?;
+
+        let mut record = Record::parse_fields(fields)?;
+        record.line = line;
+        record.id = Some(id.trim());
+        Ok(record)
+    }
+
+    /// Parses a record without id.
+    pub fn parse_fields(line: &'a str) -> Result<Self, RecordError> {
+        let fields = line
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(|field| {
+                let (key, value) = field
+                    .split_once(':')
+                    .or_else(|| field.rsplit_once(char::is_whitespace))
+                    .ok_or_else(|| RecordError {
+                        line: line.to_string(),
+                        kind: RecordErrorKind::InvalidField(field.to_string()),
+                    })?;
+                Ok((key.trim(), Value::parse(value.trim())))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Record {
+            line,
+            id: None,
+            fields,
+        })
+    }
+
+    pub fn error(&self, kind: RecordErrorKind) -> RecordError {
+        RecordError {
+            line: self.line.to_string(),
+            kind,
+        }
+    }
+
+    /// The number at the end of the id, e.g. `12` of `Sue 12`.
+    pub fn id_number(&self) -> Result<i64, RecordError> {
+        self.id
+            .and_then(|id| id.rsplit(' ').next()?.parse().ok())
+            .ok_or_else(|| self.error(RecordErrorKind::MissingId))
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value<'a>> {
+        self.fields
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    }
+
+    fn get_required(&self, key: &str) -> Result<Value<'a>, RecordError> {
+        self.get(key)
+            .ok_or_else(|| self.error(RecordErrorKind::MissingField(key.to_string())))
+    }
+
+    fn wrong_type(&self, key: &str, expected: &'static str) -> RecordError {
+        self.error(RecordErrorKind::WrongType {
+            key: key.to_string(),
+            expected,
+        })
+    }
+
+    pub fn int(&self, key: &str) -> Result<i64, RecordError> {
+        match self.get_required(key)? {
+            Value::Int(int) => Ok(int),
+            _ => Err(self.wrong_type(key, "an integer")),
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Result<bool, RecordError> {
+        match self.get_required(key)? {
+            Value::Bool(b) => Ok(b),
+            _ => Err(self.wrong_type(key, "a boolean")),
+        }
+    }
+
+    pub fn word(&self, key: &str) -> Result<&'a str, RecordError> {
+        match self.get_required(key)? {
+            Value::Word(word) => Ok(word),
+            _ => Err(self.wrong_type(key, "a word")),
+        }
+    }
+
+    pub fn deserialize<T: FromRecord<'a>>(&self) -> Result<T, RecordError> {
+        T::from_record(self)
+    }
+}
+
+/// Types that can be built from a [`Record`], usually by looking up their fields by name.
+pub trait FromRecord<'a>: Sized {
+    fn from_record(record: &Record<'a>) -> Result<Self, RecordError>;
+}
+
+/// Parses every line as a record with id and deserializes it.
+pub fn parse_records<'a, T: FromRecord<'a>>(input: &'a str) -> Result<Vec<T>, RecordError> {
+    input
+        .lines()
+        .map(|line| Record::parse(line)?.deserialize())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +657,173 @@ mod tests {
         assert_eq!(split.unwrap_next_int::<usize>(), 3);
     }
 
+    #[test]
+    fn test_tokens() {
+        let mut tokens = Tokens::new("London to Dublin = 464");
+        assert_eq!(tokens.next_word(), Ok("London"));
+        assert_eq!(tokens.expect_word("to"), Ok(()));
+        assert_eq!(tokens.next_word(), Ok("Dublin"));
+        assert_eq!(tokens.expect_words("="), Ok(()));
+        assert_eq!(tokens.next_int::<u64>(), Ok(464));
+        assert_eq!(tokens.next(), None);
+    }
+
+    #[test]
+    fn test_tokens_errors() {
+        let mut tokens = Tokens::new("London to Dublin = far");
+        let error = tokens.expect_word("from").unwrap_err();
+        assert_eq!(error.position, 0);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::UnexpectedWord("from".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            "token 0 of \"London to Dublin = far\": expected \"from\" but found \"London\"."
+        );
+
+        tokens.expect_words("to Dublin =").unwrap();
+        let error = tokens.next_int::<u64>().unwrap_err();
+        assert_eq!(error.position, 4);
+        assert_eq!(error.token.as_deref(), Some("far"));
+        assert!(matches!(error.kind, ParseErrorKind::InvalidInt(_)));
+
+        let error = tokens.next_word().unwrap_err();
+        assert_eq!(error.position, 5);
+        assert_eq!(error.kind, ParseErrorKind::MissingToken);
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern = Pattern::new("{} to {} = {}");
+        assert_eq!(pattern.capture_count(), 3);
+        assert_eq!(
+            pattern.captures("London to Dublin = 464"),
+            Ok(vec!["London", "Dublin", "464"])
+        );
+
+        let (from, to, distance): (&str, &str, u64) =
+            pattern.parse("London to Dublin = 464").unwrap();
+        assert_eq!((from, to, distance), ("London", "Dublin", 464));
+
+        let (name, amount): (String, i32) = crate::parse_pattern!("[{}] {}!", "[a b] -3!").unwrap();
+        assert_eq!((name.as_str(), amount), ("a b", -3));
+    }
+
+    #[test]
+    fn test_pattern_errors() {
+        let pattern = Pattern::new("{} to {} = {}");
+
+        let error = pattern.captures("London from Dublin = 464").unwrap_err();
+        assert_eq!(
+            error.kind,
+            PatternErrorKind::MissingLiteral {
+                literal: " to ".to_string(),
+                at: 0
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "\"London from Dublin = 464\" does not match \"{} to {} = {}\": expected \" to \" at byte 0."
+        );
+
+        let error = pattern
+            .parse::<(&str, &str, u64)>("London to Dublin = far")
+            .unwrap_err();
+        assert!(matches!(
+            error.kind,
+            PatternErrorKind::InvalidCapture { index: 2, .. }
+        ));
+
+        let error = pattern
+            .parse::<(&str, &str)>("London to Dublin = 464")
+            .unwrap_err();
+        assert_eq!(
+            error.kind,
+            PatternErrorKind::CaptureCount {
+                expected: 2,
+                found: 3
+            }
+        );
+
+        let error = Pattern::new("a{}.").captures("a1.2").unwrap_err();
+        assert_eq!(error.kind, PatternErrorKind::TrailingText("2".to_string()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_adjacent_captures() {
+        Pattern::new("{}{}");
+    }
+
+    #[test]
+    fn test_record() {
+        let record = Record::parse("Sue 12: cats: 7, trees: 3, kind: tabby, happy: true").unwrap();
+        assert_eq!(record.id, Some("Sue 12"));
+        assert_eq!(record.id_number(), Ok(12));
+        assert_eq!(record.int("cats"), Ok(7));
+        assert_eq!(record.get("trees"), Some(Value::Int(3)));
+        assert_eq!(record.word("kind"), Ok("tabby"));
+        assert_eq!(record.bool("happy"), Ok(true));
+        assert_eq!(record.get("cars"), None);
+
+        let record = Record::parse("Butterscotch: capacity -1, flavor 6").unwrap();
+        assert_eq!(record.id, Some("Butterscotch"));
+        assert_eq!(
+            record.fields,
+            vec![("capacity", Value::Int(-1)), ("flavor", Value::Int(6))]
+        );
+        assert!(record.id_number().is_err());
+
+        let record = Record::parse_fields("a: 1, b 2").unwrap();
+        assert_eq!(record.id, None);
+        assert_eq!(record.fields.len(), 2);
+    }
+
+    #[test]
+    fn test_record_errors() {
+        assert_eq!(
+            Record::parse("capacity -1").unwrap_err().kind,
+            RecordErrorKind::MissingId
+        );
+        assert_eq!(
+            Record::parse("a: capacity").unwrap_err().kind,
+            RecordErrorKind::InvalidField("capacity".to_string())
+        );
+
+        let record = Record::parse("a: b 1, c x").unwrap();
+        assert_eq!(
+            record.int("d").unwrap_err().kind,
+            RecordErrorKind::MissingField("d".to_string())
+        );
+        assert_eq!(
+            record.int("c").unwrap_err().to_string(),
+            "record \"a: b 1, c x\": expected field \"c\" to be an integer."
+        );
+    }
+
+    #[test]
+    fn test_from_record() {
+        #[derive(Debug, PartialEq)]
+        struct Point {
+            x: i64,
+            y: i64,
+        }
+
+        impl FromRecord<'_> for Point {
+            fn from_record(record: &Record<'_>) -> Result<Self, RecordError> {
+                Ok(Point {
+                    x: record.int("x")?,
+                    y: record.int("y")?,
+                })
+            }
+        }
+
+        let points: Vec<Point> = parse_records("p: x 1, y 2\nq: y 4, x 3").unwrap();
+        assert_eq!(points, vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]);
+        assert!(parse_records::<Point>("p: x 1").is_err());
+    }
+
     #[test]
     fn test_iter_ints() {
         let mut v: Vec<i64> = "aaaa 123 bbbb3434 cccc -1123".iter_ints().collect();
@@ -0,0 +1,331 @@
+use anyhow::{Context, bail};
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::utils::coord_2d::Coord;
+
+/// Integer types that can be used as components of a [`Point`].
+pub trait Scalar:
+    Copy + Debug + Display + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+
+    /// Absolute difference, which is representable for unsigned types too.
+    fn abs_diff(self, other: Self) -> Self;
+
+    /// `self + delta`, or `None` if the result does not fit into the type.
+    fn offset(self, delta: i8) -> Option<Self>;
+}
+
+macro_rules! impl_signed_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const ZERO: Self = 0;
+
+            fn abs_diff(self, other: Self) -> Self {
+                self.max(other) - self.min(other)
+            }
+
+            fn offset(self, delta: i8) -> Option<Self> {
+                self.checked_add(delta.into())
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_unsigned_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const ZERO: Self = 0;
+
+            fn abs_diff(self, other: Self) -> Self {
+                <$t>::abs_diff(self, other)
+            }
+
+            fn offset(self, delta: i8) -> Option<Self> {
+                self.checked_add_signed(delta.into())
+            }
+        }
+    )*};
+}
+
+impl_signed_scalar!(i16, i32, i64, isize);
+impl_unsigned_scalar!(u16, u32, u64, usize);
+
+/// A point or vector with `N` components, e.g. `Point<i64, 3>` for a position in space.
+/// Ordered component by component, starting with the first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T, const N: usize> {
+    pub coords: [T; N],
+}
+
+pub type Point3 = Point<i64, 3>;
+pub type Point4 = Point<i64, 4>;
+
+impl<T: Scalar, const N: usize> Point<T, N> {
+    pub const fn new(coords: [T; N]) -> Self {
+        Point { coords }
+    }
+
+    pub fn origin() -> Self {
+        Point::new([T::ZERO; N])
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        self.coords
+            .iter()
+            .zip(other.coords)
+            .fold(T::ZERO, |sum, (a, b)| sum + a.abs_diff(b))
+    }
+
+    /// The largest distance along a single axis.
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        self.coords
+            .iter()
+            .zip(other.coords)
+            .fold(T::ZERO, |max, (a, b)| max.max(a.abs_diff(b)))
+    }
+
+    /// The `2 * N` points differing by one along exactly one axis.
+    /// Points that would leave the range of `T` are skipped.
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..N).flat_map(move |axis| {
+            [-1, 1].into_iter().filter_map(move |delta| {
+                let mut neighbor = *self;
+                neighbor.coords[axis] = self.coords[axis].offset(delta)?;
+                Some(neighbor)
+            })
+        })
+    }
+
+    /// The up to `3^N - 1` points differing by at most one along every axis.
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> + '_ {
+        let count = 3_usize.pow(N as u32);
+
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .filter_map(move |mut i| {
+                // every digit of `i` in base 3 selects the offset -1, 0 or 1 of one axis.
+                let mut neighbor = *self;
+                for axis in 0..N {
+                    let delta = (i % 3) as i8 - 1;
+                    neighbor.coords[axis] = self.coords[axis].offset(delta)?;
+                    i /= 3;
+                }
+                Some(neighbor)
+            })
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.coords[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Point<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.coords[axis]
+    }
+}
+
+impl<T: Scalar, const N: usize> Add for Point<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(std::array::from_fn(|i| self.coords[i] + rhs.coords[i]))
+    }
+}
+
+impl<T: Scalar, const N: usize> AddAssign for Point<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> Sub for Point<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(std::array::from_fn(|i| self.coords[i] - rhs.coords[i]))
+    }
+}
+
+impl<T: Scalar, const N: usize> SubAssign for Point<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Scalar, const N: usize> Mul<T> for Point<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point::new(self.coords.map(|c| c * rhs))
+    }
+}
+
+impl<T: Display, const N: usize> Display for Point<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, c) in self.coords.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> FromStr for Point<T, N>
+where
+    T: Scalar + FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    /// Parses comma-separated components, e.g. `1,-2,3` or `<1, -2, 3>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s
+            .trim()
+            .trim_start_matches(['(', '<'])
+            .trim_end_matches([')', '>']);
+        let mut coords = [T::ZERO; N];
+        let mut parts = s.split(',');
+
+        for (axis, coord) in coords.iter_mut().enumerate() {
+            let part = parts
+                .next()
+                .with_context(|| format!("Bad format, component {axis} not found."))?;
+            *coord = part.trim().parse()?;
+        }
+
+        if parts.next().is_some() {
+            bail!("Bad format, expected {N} components.");
+        }
+
+        Ok(Point::new(coords))
+    }
+}
+
+impl From<Coord> for Point<i64, 2> {
+    fn from(value: Coord) -> Self {
+        Point::new([value.x, value.y])
+    }
+}
+
+impl From<Point<i64, 2>> for Coord {
+    fn from(value: Point<i64, 2>) -> Self {
+        Coord::new(value[0], value[1])
+    }
+}
+
+/// The smallest axis-aligned box containing a set of points, both corners included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundingBox<T, const N: usize> {
+    pub min: Point<T, N>,
+    pub max: Point<T, N>,
+}
+
+impl<T: Scalar, const N: usize> BoundingBox<T, N> {
+    /// Returns `None` if there are no points.
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point<T, N>>) -> Option<Self>
+    where
+        T: 'a,
+    {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        let mut bounds = BoundingBox {
+            min: first,
+            max: first,
+        };
+        points.for_each(|p| bounds.include(p));
+        Some(bounds)
+    }
+
+    /// Grows the box so that it contains `point`.
+    pub fn include(&mut self, point: &Point<T, N>) {
+        for axis in 0..N {
+            self.min[axis] = self.min[axis].min(point[axis]);
+            self.max[axis] = self.max[axis].max(point[axis]);
+        }
+    }
+
+    pub fn contains(&self, point: &Point<T, N>) -> bool {
+        (0..N).all(|axis| (self.min[axis]..=self.max[axis]).contains(&point[axis]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Point3::from_str("1,-2,3").unwrap(), Point::new([1, -2, 3]));
+        assert_eq!(
+            Point::<u32, 2>::from_str("<4, 5>").unwrap(),
+            Point::new([4, 5])
+        );
+        assert!(Point3::from_str("1,2").is_err());
+        assert!(Point3::from_str("1,2,3,4").is_err());
+        assert!(Point::<u32, 2>::from_str("-1,2").is_err());
+        assert_eq!(Point::new([1, -2, 3]).to_string(), "1,-2,3");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new([1, 2, 3, 4]);
+        let b = Point::new([4, 3, 2, 1]);
+        assert_eq!(a + b, Point::new([5, 5, 5, 5]));
+        assert_eq!(a - b, Point::new([-3, -1, 1, 3]));
+        assert_eq!(a * 2, Point::new([2, 4, 6, 8]));
+        assert_eq!(a.manhattan_distance(&b), 8);
+        assert_eq!(a.chebyshev_distance(&b), 3);
+        assert_eq!(Point::<u16, 1>::origin(), Point::new([0]));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point3::origin();
+        assert_eq!(p.neighbors().count(), 26);
+        assert!(p.neighbors().all(|n| p.chebyshev_distance(&n) == 1));
+        assert_eq!(p.orthogonal_neighbors().count(), 6);
+        assert!(
+            p.orthogonal_neighbors()
+                .all(|n| p.manhattan_distance(&n) == 1)
+        );
+        assert_eq!(Point4::origin().neighbors().count(), 80);
+
+        // unsigned points at the origin only have neighbors in the positive directions.
+        let corner = Point::<usize, 2>::origin();
+        assert_eq!(corner.neighbors().count(), 3);
+        assert_eq!(corner.orthogonal_neighbors().count(), 2);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [
+            Point3::new([1, 5, -2]),
+            Point3::new([-3, 2, 0]),
+            Point3::new([2, 2, 7]),
+        ];
+        let bounds = BoundingBox::from_points(&points).unwrap();
+        assert_eq!(bounds.min, Point3::new([-3, 2, -2]));
+        assert_eq!(bounds.max, Point3::new([2, 5, 7]));
+        assert!(points.iter().all(|p| bounds.contains(p)));
+        assert!(!bounds.contains(&Point3::origin()));
+        assert_eq!(BoundingBox::<i64, 3>::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_coord_conversion() {
+        let c = Coord::new(3, -4);
+        let p = Point::from(c);
+        assert_eq!(p, Point::new([3, -4]));
+        assert_eq!(Coord::from(p), c);
+    }
+}
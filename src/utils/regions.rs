@@ -0,0 +1,161 @@
+use crate::utils::coord_2d::Coord;
+use crate::utils::geom::PositionalRectangle;
+use crate::utils::grid::Grid;
+
+/// A grid over coordinate-compressed space: every cell stands for a rectangular region in which
+/// all coordinates were covered by exactly the same rectangles.
+/// Only the regions between the breakpoints of the registered rectangles are represented,
+/// coordinates outside of all of them are never stored.
+pub struct CompressedGrid<T> {
+    _xs: Vec<i64>,
+    _ys: Vec<i64>,
+    _cells: Grid<T>,
+}
+
+/// Sorted distinct coordinates at which a rectangle starts or ends (exclusive).
+fn breakpoints(rects: &[PositionalRectangle], axis: impl Fn(Coord) -> i64) -> Vec<i64> {
+    let mut points: Vec<i64> = rects
+        .iter()
+        .flat_map(|r| [axis(r.top_corner()), axis(r.bottom_corner()) + 1])
+        .collect();
+    points.sort_unstable();
+    points.dedup();
+    points
+}
+
+impl<T: Default + Clone> CompressedGrid<T> {
+    /// Creates a grid whose regions are split along the edges of all `rects`.
+    pub fn new(rects: &[PositionalRectangle]) -> Self {
+        let xs = breakpoints(rects, |c| c.x);
+        let ys = breakpoints(rects, |c| c.y);
+        let cells = Grid::new(xs.len().saturating_sub(1), ys.len().saturating_sub(1));
+
+        CompressedGrid {
+            _xs: xs,
+            _ys: ys,
+            _cells: cells,
+        }
+    }
+
+    /// Applies every `(rectangle, operation)` instruction in order, `apply` updates a single region.
+    pub fn evaluate<Op>(
+        instructions: &[(PositionalRectangle, Op)],
+        mut apply: impl FnMut(&mut T, &Op),
+    ) -> Self {
+        let rects: Vec<PositionalRectangle> = instructions.iter().map(|(r, _)| *r).collect();
+        let mut grid = CompressedGrid::new(&rects);
+
+        for (rect, op) in instructions {
+            grid.apply_in_rectangle(rect, |value| apply(value, op));
+        }
+
+        grid
+    }
+}
+
+impl<T> CompressedGrid<T> {
+    fn index_of(points: &[i64], value: i64) -> usize {
+        points
+            .binary_search(&value)
+            .expect("rectangle was not registered with the grid")
+    }
+
+    /// Number of compressed regions.
+    pub fn region_count(&self) -> usize {
+        self._cells.width() * self._cells.height()
+    }
+
+    /// Calls `func` on every region covered by `rect`, which has to be one of the registered rectangles.
+    pub fn apply_in_rectangle(&mut self, rect: &PositionalRectangle, mut func: impl FnMut(&mut T)) {
+        let x_start = Self::index_of(&self._xs, rect.top_corner().x);
+        let x_end = Self::index_of(&self._xs, rect.bottom_corner().x + 1);
+        let y_start = Self::index_of(&self._ys, rect.top_corner().y);
+        let y_end = Self::index_of(&self._ys, rect.bottom_corner().y + 1);
+
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                func(&mut self._cells[Coord::new(x as i64, y as i64)]);
+            }
+        }
+    }
+
+    /// Every region with its value, row by row.
+    pub fn iter_regions(&self) -> impl Iterator<Item = (PositionalRectangle, &T)> {
+        self._ys.windows(2).enumerate().flat_map(move |(y, ys)| {
+            self._xs.windows(2).enumerate().map(move |(x, xs)| {
+                let rect = PositionalRectangle::new(
+                    Coord::new(xs[0], ys[0]),
+                    Coord::new(xs[1] - 1, ys[1] - 1),
+                );
+                (rect, &self._cells[Coord::new(x as i64, y as i64)])
+            })
+        })
+    }
+
+    /// Sums `weight` over all represented coordinates, i.e. per region weighted by its area.
+    pub fn sum_by(&self, mut weight: impl FnMut(&T) -> usize) -> usize {
+        self.iter_regions()
+            .map(|(rect, value)| weight(value) * rect.area())
+            .sum()
+    }
+
+    /// Number of represented coordinates whose region matches `pred`.
+    pub fn count_where(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.sum_by(|value| usize::from(pred(value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x1: i64, y1: i64, x2: i64, y2: i64) -> PositionalRectangle {
+        PositionalRectangle::new(Coord::new(x1, y1), Coord::new(x2, y2))
+    }
+
+    #[test]
+    fn test_compression() {
+        let grid = CompressedGrid::<bool>::new(&[rect(0, 0, 999, 999), rect(499, 499, 500, 500)]);
+        // x and y are both split into 0..499, 499..501 and 501..1000.
+        assert_eq!(grid.region_count(), 9);
+        assert_eq!(grid.sum_by(|_| 1), 1_000_000);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let instructions = [
+            (rect(0, 0, 999, 999), true),
+            (rect(0, 0, 999, 0), false),
+            (rect(499, 499, 500, 500), false),
+        ];
+        let grid = CompressedGrid::evaluate(&instructions, |light, on| *light = *on);
+        assert_eq!(grid.count_where(|light| *light), 1_000_000 - 1000 - 4);
+    }
+
+    #[test]
+    fn test_matches_full_grid() {
+        let instructions = [
+            (rect(2, 3, 7, 9), 1),
+            (rect(5, 0, 11, 4), 2),
+            (rect(0, 8, 3, 11), 3),
+            (rect(6, 2, 6, 10), 4),
+        ];
+
+        let mut full = Grid::<usize>::new(12, 12);
+        for (r, amount) in &instructions {
+            full.apply_in_rectangle(*r, |v| *v += amount);
+        }
+
+        let compressed =
+            CompressedGrid::evaluate(&instructions, |v: &mut usize, amount| *v += amount);
+
+        assert_eq!(
+            compressed.sum_by(|v| *v),
+            full.iter().map(|it| it.value).sum::<usize>()
+        );
+        assert_eq!(
+            compressed.count_where(|v| *v > 2),
+            full.iter().filter(|it| it.value > 2).count()
+        );
+    }
+}
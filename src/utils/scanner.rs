@@ -0,0 +1,236 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+/// Integer types an [`IntScanner`] can produce.
+pub trait ScanInt: Copy + 'static {
+    const ZERO: Self;
+
+    /// Appends a digit to `self`, away from zero in the direction of the sign.
+    /// Returns `None` if the result does not fit into the type.
+    fn push_digit(self, radix: u8, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_scan_int {
+    ($($t:ty),*) => {$(
+        impl ScanInt for $t {
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn push_digit(self, radix: u8, digit: u8, negative: bool) -> Option<Self> {
+                let shifted = self.checked_mul(radix as $t)?;
+                if negative {
+                    shifted.checked_sub(digit as $t)
+                } else {
+                    shifted.checked_add(digit as $t)
+                }
+            }
+        }
+    )*};
+}
+
+impl_scan_int!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Radix {
+    #[default]
+    Decimal,
+    /// Hexadecimal digits in either case, with an optional `0x` prefix.
+    Hex,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScanOptions {
+    pub radix: Radix,
+    /// Whether a `-` directly in front of a number makes it negative.
+    /// If not, every number is read as unsigned and `-` is treated as a separator.
+    pub signed: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            radix: Radix::Decimal,
+            signed: true,
+        }
+    }
+}
+
+/// A number that does not fit into the requested type, with its byte range in the input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "number at bytes {}..{} is out of range.",
+            self.start, self.end
+        )
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
+#[inline]
+fn digit_value(byte: u8, radix: Radix) -> Option<u8> {
+    match (byte, radix) {
+        (b'0'..=b'9', _) => Some(byte - b'0'),
+        (b'a'..=b'f', Radix::Hex) => Some(byte - b'a' + 10),
+        (b'A'..=b'F', Radix::Hex) => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Finds the integers in a byte slice without allocating, everything else counts as separator.
+pub struct IntScanner<'a, T> {
+    _bytes: &'a [u8],
+    _position: usize,
+    _options: ScanOptions,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: ScanInt> IntScanner<'a, T> {
+    pub fn new(bytes: &'a [u8], options: ScanOptions) -> Self {
+        IntScanner {
+            _bytes: bytes,
+            _position: 0,
+            _options: options,
+            _marker: PhantomData,
+        }
+    }
+
+    fn digit_at(&self, index: usize) -> Option<u8> {
+        digit_value(*self._bytes.get(index)?, self._options.radix)
+    }
+}
+
+impl<T: ScanInt> Iterator for IntScanner<'_, T> {
+    type Item = Result<T, OutOfRange>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self._bytes;
+        let radix = self._options.radix;
+
+        // fast path: skip separators until the first digit.
+        let rest = &bytes[self._position..];
+        let offset = match radix {
+            Radix::Decimal => rest.iter().position(u8::is_ascii_digit)?,
+            Radix::Hex => rest.iter().position(u8::is_ascii_hexdigit)?,
+        };
+        let mut start = self._position + offset;
+
+        // the sign is only looked at once a digit was found, a trailing `-` is never read past.
+        let negative = self._options.signed && start > 0 && bytes[start - 1] == b'-';
+        let number_start = start - usize::from(negative);
+
+        let (radix_value, mut end) = match radix {
+            Radix::Decimal => (10, start),
+            Radix::Hex => {
+                let has_prefix = bytes[start] == b'0'
+                    && matches!(bytes.get(start + 1), Some(b'x' | b'X'))
+                    && self.digit_at(start + 2).is_some();
+                if has_prefix {
+                    start += 2;
+                }
+                (16, start)
+            }
+        };
+
+        let mut value = Some(T::ZERO);
+        while let Some(digit) = self.digit_at(end) {
+            value = value.and_then(|v| v.push_digit(radix_value, digit, negative));
+            end += 1;
+        }
+
+        self._position = end;
+
+        Some(value.ok_or(OutOfRange {
+            start: number_start,
+            end,
+        }))
+    }
+}
+
+/// All decimal integers in `bytes`, numbers out of range of `T` are skipped.
+pub fn scan_ints<T: ScanInt>(bytes: &[u8]) -> impl Iterator<Item = T> + '_ {
+    IntScanner::new(bytes, ScanOptions::default()).filter_map(Result::ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan<T: ScanInt>(text: &str, options: ScanOptions) -> Vec<Result<T, OutOfRange>> {
+        IntScanner::new(text.as_bytes(), options).collect()
+    }
+
+    #[test]
+    fn test_decimal() {
+        let ints: Vec<i64> = scan_ints(b"aaaa 123 bbbb3434 cccc -1123").collect();
+        assert_eq!(ints, vec![123, 3434, -1123]);
+
+        let ints: Vec<i64> = scan_ints(b"[1,{\"c\":-2},3]").collect();
+        assert_eq!(ints, vec![1, -2, 3]);
+
+        let ints: Vec<i64> = scan_ints(b"").collect();
+        assert!(ints.is_empty());
+    }
+
+    #[test]
+    fn test_trailing_sign() {
+        let ints: Vec<i64> = scan_ints(b"5 - 3 -").collect();
+        assert_eq!(ints, vec![5, 3]);
+
+        let ints: Vec<i64> = scan_ints(b"-").collect();
+        assert!(ints.is_empty());
+
+        let ints: Vec<i64> = scan_ints(b"--4").collect();
+        assert_eq!(ints, vec![-4]);
+    }
+
+    #[test]
+    fn test_unsigned() {
+        let unsigned = ScanOptions {
+            signed: false,
+            ..ScanOptions::default()
+        };
+        assert_eq!(scan::<u32>("2-3-4", unsigned), vec![Ok(2), Ok(3), Ok(4)]);
+        assert_eq!(
+            scan::<u32>("1 -0 -2", ScanOptions::default()),
+            vec![Ok(1), Ok(0), Err(OutOfRange { start: 5, end: 7 })]
+        );
+    }
+
+    #[test]
+    fn test_hex() {
+        let hex = ScanOptions {
+            radix: Radix::Hex,
+            ..ScanOptions::default()
+        };
+        assert_eq!(
+            scan::<i64>("ff 0x1A, -10 0xg", hex),
+            vec![Ok(255), Ok(26), Ok(-16), Ok(0)]
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(
+            scan::<u8>("255 256 7", ScanOptions::default()),
+            vec![Ok(255), Err(OutOfRange { start: 4, end: 7 }), Ok(7)]
+        );
+        assert_eq!(
+            scan::<i8>("-128 -129", ScanOptions::default()),
+            vec![Ok(-128), Err(OutOfRange { start: 5, end: 9 })]
+        );
+        assert_eq!(
+            scan::<i64>("-9223372036854775808", ScanOptions::default()),
+            vec![Ok(i64::MIN)]
+        );
+    }
+}
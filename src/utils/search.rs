@@ -0,0 +1,549 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+use std::rc::Rc;
+
+use crate::utils::graph::Goal;
+
+/// The states of a search and the moves between them.
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+    type Cost: Copy + Ord + Default + Add<Output = Self::Cost>;
+
+    /// The states reachable from `state` with a single move, with the cost of the move.
+    fn successors(&self, state: &Self::State) -> impl Iterator<Item = (Self::State, Self::Cost)>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// An estimate of the cost from `state` to the nearest goal, used by [`astar`].
+    /// It must never overestimate for the result to be optimal.
+    fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+        Self::Cost::default()
+    }
+}
+
+/// Whether a search keeps track of how states were reached, to return the path to the goal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathMode {
+    #[default]
+    CostOnly,
+    WithPath,
+}
+
+/// The cost of the best way to a goal and, with [`PathMode::WithPath`],
+/// the states along it from the start to the goal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution<S, C> {
+    pub cost: C,
+    pub path: Option<Vec<S>>,
+}
+
+const NO_PARENT: usize = usize::MAX;
+
+/// Every state seen by a search, stored once and referred to by index.
+struct Visited<S> {
+    _states: Vec<S>,
+    _parents: Vec<usize>,
+    _index: HashMap<S, usize>,
+    _mode: PathMode,
+}
+
+impl<S: Clone + Eq + Hash> Visited<S> {
+    fn new(mode: PathMode) -> Self {
+        Visited {
+            _states: vec![],
+            _parents: vec![],
+            _index: HashMap::new(),
+            _mode: mode,
+        }
+    }
+
+    /// The index of `state`, and whether it was seen for the first time.
+    fn insert(&mut self, state: S, parent: usize) -> (usize, bool) {
+        if let Some(&index) = self._index.get(&state) {
+            return (index, false);
+        }
+
+        let index = self._states.len();
+        self._states.push(state.clone());
+        self._parents.push(parent);
+        self._index.insert(state, index);
+        (index, true)
+    }
+
+    fn set_parent(&mut self, index: usize, parent: usize) {
+        self._parents[index] = parent;
+    }
+
+    fn solution<C>(&self, cost: C, goal: usize) -> Solution<S, C> {
+        let path = (self._mode == PathMode::WithPath).then(|| {
+            let mut path = vec![];
+            let mut current = goal;
+            while current != NO_PARENT {
+                path.push(self._states[current].clone());
+                current = self._parents[current];
+            }
+            path.reverse();
+            path
+        });
+
+        Solution { cost, path }
+    }
+}
+
+/// The goal with the fewest moves from `start`, the cost is the number of moves.
+pub fn bfs<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    mode: PathMode,
+) -> Option<Solution<P::State, usize>> {
+    let mut visited = Visited::new(mode);
+    let (start, _) = visited.insert(start, NO_PARENT);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((index, steps)) = queue.pop_front() {
+        let state = visited._states[index].clone();
+        if problem.is_goal(&state) {
+            return Some(visited.solution(steps, index));
+        }
+
+        for (next, _) in problem.successors(&state) {
+            let (next, new) = visited.insert(next, index);
+            if new {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// The cheapest goal from `start`, all move costs have to be non-negative.
+pub fn dijkstra<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    mode: PathMode,
+) -> Option<Solution<P::State, P::Cost>> {
+    best_first(problem, start, mode, |_, _| P::Cost::default())
+}
+
+/// Like [`dijkstra`], exploring states in order of cost plus [`SearchProblem::heuristic`].
+pub fn astar<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    mode: PathMode,
+) -> Option<Solution<P::State, P::Cost>> {
+    best_first(problem, start, mode, |problem, state| {
+        problem.heuristic(state)
+    })
+}
+
+fn best_first<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    mode: PathMode,
+    estimate: impl Fn(&P, &P::State) -> P::Cost,
+) -> Option<Solution<P::State, P::Cost>> {
+    let mut visited = Visited::new(mode);
+    let mut costs: Vec<P::Cost> = vec![];
+    let mut queue = BinaryHeap::new();
+
+    let zero = P::Cost::default();
+    let (start_index, _) = visited.insert(start.clone(), NO_PARENT);
+    costs.push(zero);
+    queue.push(Reverse((estimate(problem, &start), zero, start_index)));
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // a cheaper way to this state was found after it was queued.
+        if cost > costs[index] {
+            continue;
+        }
+
+        let state = visited._states[index].clone();
+        if problem.is_goal(&state) {
+            return Some(visited.solution(cost, index));
+        }
+
+        for (next, move_cost) in problem.successors(&state) {
+            let next_cost = cost + move_cost;
+            let priority = next_cost + estimate(problem, &next);
+            let (next_index, new) = visited.insert(next, index);
+
+            if new {
+                costs.push(next_cost);
+            } else if next_cost < costs[next_index] {
+                costs[next_index] = next_cost;
+                visited.set_parent(next_index, index);
+            } else {
+                continue;
+            }
+            queue.push(Reverse((priority, next_cost, next_index)));
+        }
+    }
+
+    None
+}
+
+/// A path to a goal as a linked list, so that results can share the rest of their path.
+struct PathNode<S> {
+    _state: S,
+    _next: Option<Rc<PathNode<S>>>,
+}
+
+impl<S> Drop for PathNode<S> {
+    // unlinks the rest of the path one node at a time, long paths would overflow the stack
+    // with the recursive default.
+    fn drop(&mut self) {
+        let mut next = self._next.take();
+        while let Some(node) = next {
+            next = match Rc::try_unwrap(node) {
+                Ok(mut node) => node._next.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+/// The best cost to a goal and, with [`PathMode::WithPath`], the path there.
+/// `None` for dead ends.
+type Best<S, C> = Option<(C, Option<Rc<PathNode<S>>>)>;
+
+/// A state whose successors are being explored by [`dfs_memo`].
+struct Frame<S, C> {
+    _state: S,
+    _successors: Vec<(S, C)>,
+    _next: usize,
+    _best: Best<S, C>,
+    /// The lowest stack depth of a state that was skipped because it was still being explored,
+    /// by this state or its descendants. The result only holds while that state is on the stack.
+    _low: usize,
+}
+
+/// The best cost from `start` to a goal, found by depth-first search that remembers the
+/// best cost from every explored state, so shared sub-problems are solved once.
+/// Unlike the other searches it can maximize. Paths never visit a state twice, moves back to
+/// a state that is still being explored are ignored. A result that ignored such a move depends
+/// on the states being explored, so it is not remembered, and cyclic moves can take
+/// exponential time.
+/// Runs with an explicit stack, so deep searches do not overflow.
+pub fn dfs_memo<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    goal: Goal,
+    mode: PathMode,
+) -> Option<Solution<P::State, P::Cost>> {
+    let mut memo: HashMap<P::State, Best<P::State, P::Cost>> = HashMap::new();
+    // the stack depth of every state being explored.
+    let mut in_progress: HashMap<P::State, usize> = HashMap::new();
+
+    let frame = |state: P::State, depth: usize| {
+        let successors = problem.successors(&state).collect();
+        Frame {
+            _state: state,
+            _successors: successors,
+            _next: 0,
+            _best: None,
+            _low: depth,
+        }
+    };
+    let update =
+        |best: &mut Best<P::State, P::Cost>, cost, path: &Option<Rc<PathNode<P::State>>>| {
+            if goal.is_better(cost, best.as_ref().map(|(cost, _)| *cost)) {
+                *best = Some((cost, path.clone()));
+            }
+        };
+    let reached = |state: &P::State, best: Best<P::State, P::Cost>| {
+        best.map(|(cost, next)| {
+            let path = (mode == PathMode::WithPath).then(|| {
+                Rc::new(PathNode {
+                    _state: state.clone(),
+                    _next: next,
+                })
+            });
+            (cost, path)
+        })
+    };
+    let goal_reached = |state: &P::State| reached(state, Some((P::Cost::default(), None)));
+
+    if problem.is_goal(&start) {
+        memo.insert(start.clone(), goal_reached(&start));
+    } else {
+        in_progress.insert(start.clone(), 0);
+        let mut stack = vec![frame(start.clone(), 0)];
+
+        while let Some(top) = stack.last_mut() {
+            if let Some((next, move_cost)) = top._successors.get(top._next).cloned() {
+                top._next += 1;
+
+                if let Some(result) = memo.get(&next) {
+                    if let Some((cost, path)) = result {
+                        update(&mut top._best, move_cost + *cost, path);
+                    }
+                } else if problem.is_goal(&next) {
+                    let result = goal_reached(&next);
+                    if let Some((cost, path)) = &result {
+                        update(&mut top._best, move_cost + *cost, path);
+                    }
+                    memo.insert(next, result);
+                } else if let Some(&depth) = in_progress.get(&next) {
+                    top._low = top._low.min(depth);
+                } else {
+                    let depth = stack.len();
+                    in_progress.insert(next.clone(), depth);
+                    stack.push(frame(next, depth));
+                }
+                continue;
+            }
+
+            let finished = stack.pop().expect("stack is not empty");
+            in_progress.remove(&finished._state);
+            let result = reached(&finished._state, finished._best);
+
+            if let Some(parent) = stack.last_mut() {
+                parent._low = parent._low.min(finished._low);
+                if let Some((cost, path)) = &result {
+                    let move_cost = parent._successors[parent._next - 1].1;
+                    update(&mut parent._best, move_cost + *cost, path);
+                }
+            }
+
+            // only results that did not skip a state below on the stack hold for every path here.
+            if finished._low >= stack.len() {
+                memo.insert(finished._state, result);
+            }
+        }
+    }
+
+    let (cost, mut node) = memo.remove(&start)??;
+    let path = (mode == PathMode::WithPath).then(|| {
+        let mut path = vec![];
+        while let Some(current) = node {
+            path.push(current._state.clone());
+            node = current._next.clone();
+        }
+        path
+    });
+
+    Some(Solution { cost, path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A weighted directed graph given as edge list, with a single goal node.
+    struct Edges {
+        edges: Vec<(u32, u32, u64)>,
+        goal: u32,
+    }
+
+    impl SearchProblem for Edges {
+        type State = u32;
+        type Cost = u64;
+
+        fn successors(&self, state: &u32) -> impl Iterator<Item = (u32, u64)> {
+            let state = *state;
+            self.edges
+                .iter()
+                .filter(move |(from, _, _)| *from == state)
+                .map(|(_, to, cost)| (*to, *cost))
+        }
+
+        fn is_goal(&self, state: &u32) -> bool {
+            *state == self.goal
+        }
+    }
+
+    /// A maze of `#` walls, moving between open cells costs 1.
+    struct Maze {
+        cells: Vec<Vec<bool>>,
+        goal: (usize, usize),
+    }
+
+    impl Maze {
+        fn parse(text: &str, goal: (usize, usize)) -> Self {
+            let cells = text
+                .lines()
+                .map(|line| line.chars().map(|c| c != '#').collect())
+                .collect();
+            Maze { cells, goal }
+        }
+    }
+
+    impl SearchProblem for Maze {
+        type State = (usize, usize);
+        type Cost = usize;
+
+        fn successors(
+            &self,
+            &(x, y): &(usize, usize),
+        ) -> impl Iterator<Item = ((usize, usize), usize)> {
+            [(1, 0), (0, 1), (-1, 0), (0, -1)]
+                .into_iter()
+                .filter_map(move |(dx, dy)| {
+                    let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+                    self.cells.get(next.1)?.get(next.0)?.then_some((next, 1))
+                })
+        }
+
+        fn is_goal(&self, state: &(usize, usize)) -> bool {
+            *state == self.goal
+        }
+
+        fn heuristic(&self, &(x, y): &(usize, usize)) -> usize {
+            x.abs_diff(self.goal.0) + y.abs_diff(self.goal.1)
+        }
+    }
+
+    const MAZE: &str = "\
+.....#....
+.###.#.##.
+.#...#..#.
+.#.####.#.
+.#......#.
+.########.
+..........";
+
+    fn weighted() -> Edges {
+        // the direct edge is expensive, the detour through 1 and 2 is cheap.
+        Edges {
+            edges: vec![
+                (0, 3, 10),
+                (0, 1, 1),
+                (1, 2, 2),
+                (2, 3, 3),
+                (3, 4, 1),
+                (1, 4, 20),
+            ],
+            goal: 4,
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let solution = bfs(&weighted(), 0, PathMode::WithPath).unwrap();
+        assert_eq!(solution.cost, 2);
+        assert_eq!(solution.path, Some(vec![0, 3, 4]));
+
+        let maze = Maze::parse(MAZE, (6, 2));
+        let solution = bfs(&maze, (0, 0), PathMode::CostOnly).unwrap();
+        assert_eq!(
+            solution,
+            Solution {
+                cost: 18,
+                path: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let solution = dijkstra(&weighted(), 0, PathMode::WithPath).unwrap();
+        assert_eq!(solution.cost, 7);
+        assert_eq!(solution.path, Some(vec![0, 1, 2, 3, 4]));
+
+        let start_is_goal = dijkstra(&weighted(), 4, PathMode::WithPath).unwrap();
+        assert_eq!(start_is_goal.path, Some(vec![4]));
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = Maze::parse(MAZE, (6, 2));
+        let solution = astar(&maze, (0, 0), PathMode::WithPath).unwrap();
+        assert_eq!(solution.cost, 18);
+
+        let path = solution.path.unwrap();
+        assert_eq!(path.len(), 19);
+        assert!(
+            path.windows(2)
+                .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1)
+        );
+        assert_eq!(
+            dijkstra(&maze, (0, 0), PathMode::CostOnly).unwrap().cost,
+            18
+        );
+    }
+
+    #[test]
+    fn test_dfs_memo() {
+        let problem = weighted();
+        let shortest = dfs_memo(&problem, 0, Goal::Minimize, PathMode::WithPath).unwrap();
+        assert_eq!(shortest.cost, 7);
+        assert_eq!(shortest.path, Some(vec![0, 1, 2, 3, 4]));
+
+        let longest = dfs_memo(&problem, 0, Goal::Maximize, PathMode::WithPath).unwrap();
+        assert_eq!(longest.cost, 21);
+        assert_eq!(longest.path, Some(vec![0, 1, 4]));
+    }
+
+    #[test]
+    fn test_dfs_memo_cycles() {
+        // 2 is first explored from 1, while the move back to 1 is ignored.
+        // That result must not be reused when 2 is reached directly from 0.
+        let problem = Edges {
+            edges: vec![
+                (0, 1, 1000),
+                (0, 2, 1),
+                (1, 2, 1),
+                (1, 3, 1),
+                (2, 1, 1),
+                (2, 3, 100),
+            ],
+            goal: 3,
+        };
+
+        let expected = dijkstra(&problem, 0, PathMode::WithPath).unwrap();
+        assert_eq!(expected.cost, 3);
+        let shortest = dfs_memo(&problem, 0, Goal::Minimize, PathMode::WithPath).unwrap();
+        assert_eq!(shortest, expected);
+        assert_eq!(shortest.path, Some(vec![0, 2, 1, 3]));
+
+        let longest = dfs_memo(&problem, 0, Goal::Maximize, PathMode::WithPath).unwrap();
+        assert_eq!(longest.cost, 1101);
+        assert_eq!(longest.path, Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_deep_dfs_memo() {
+        /// `0 -> 1 -> ... -> len`, deeper than the call stack could handle recursively.
+        struct Chain {
+            len: u32,
+        }
+
+        impl SearchProblem for Chain {
+            type State = u32;
+            type Cost = u64;
+
+            fn successors(&self, state: &u32) -> impl Iterator<Item = (u32, u64)> {
+                (*state < self.len).then_some((state + 1, 1)).into_iter()
+            }
+
+            fn is_goal(&self, state: &u32) -> bool {
+                *state == self.len
+            }
+        }
+
+        let chain = Chain { len: 200_000 };
+        let solution = dfs_memo(&chain, 0, Goal::Minimize, PathMode::CostOnly).unwrap();
+        assert_eq!(solution.cost, 200_000);
+
+        let solution = dfs_memo(&chain, 0, Goal::Minimize, PathMode::WithPath).unwrap();
+        assert_eq!(solution.path.map(|path| path.len()), Some(200_001));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let problem = Edges {
+            edges: vec![(0, 1, 1), (1, 0, 1), (2, 3, 1)],
+            goal: 3,
+        };
+        assert_eq!(bfs(&problem, 0, PathMode::WithPath), None);
+        assert_eq!(dijkstra(&problem, 0, PathMode::WithPath), None);
+        assert_eq!(astar(&problem, 0, PathMode::WithPath), None);
+        assert_eq!(
+            dfs_memo(&problem, 0, Goal::Minimize, PathMode::WithPath),
+            None
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+use super::coord_2d::Coord;
+use crate::utils::geom::PositionalRectangle;
+use crate::utils::grid::{Grid, GridElement, MutableGridElement};
+
+/// A grid without fixed size that only stores the cells that were set, at any coordinate,
+/// including negative ones. The bounds grow with every inserted cell, they do not shrink on removal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    _cells: HashMap<Coord, T>,
+    _bounds: Option<PositionalRectangle>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            _cells: HashMap::new(),
+            _bounds: None,
+        }
+    }
+
+    /// Builds a grid from lines of text, `func` decides which characters become cells.
+    pub fn parse(input: &str, mut func: impl FnMut(char) -> Option<T>) -> Self {
+        let mut grid = SparseGrid::new();
+
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = func(c) {
+                    grid.insert(Coord::new(x as i64, y as i64), value);
+                }
+            }
+        }
+
+        grid
+    }
+
+    fn expand_bounds(&mut self, c: Coord) {
+        self._bounds = Some(match self._bounds {
+            None => PositionalRectangle::new(c, c),
+            Some(bounds) => bounds.bounding_box(&PositionalRectangle::new(c, c)),
+        });
+    }
+
+    /// The smallest rectangle containing every cell that was ever set.
+    pub fn bounds(&self) -> Option<PositionalRectangle> {
+        self._bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self._bounds.map_or(0, |b| b.length())
+    }
+
+    pub fn height(&self) -> usize {
+        self._bounds.map_or(0, |b| b.width())
+    }
+
+    /// Number of set cells.
+    pub fn len(&self) -> usize {
+        self._cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self._cells.is_empty()
+    }
+
+    pub fn contains(&self, c: &Coord) -> bool {
+        self._cells.contains_key(c)
+    }
+
+    pub fn get(&self, c: &Coord) -> Option<&T> {
+        self._cells.get(c)
+    }
+
+    pub fn get_mut(&mut self, c: &Coord) -> Option<&mut T> {
+        self._cells.get_mut(c)
+    }
+
+    /// Sets a cell, returning its previous value.
+    pub fn insert(&mut self, c: Coord, value: T) -> Option<T> {
+        self.expand_bounds(c);
+        self._cells.insert(c, value)
+    }
+
+    pub fn remove(&mut self, c: Coord) -> Option<T> {
+        self._cells.remove(&c)
+    }
+
+    /// The cell at `c`, set to `T::default()` first if it is not set yet.
+    pub fn get_or_insert_default(&mut self, c: Coord) -> &mut T
+    where
+        T: Default,
+    {
+        if !self._cells.contains_key(&c) {
+            self.expand_bounds(c);
+        }
+        self._cells.entry(c).or_default()
+    }
+
+    /// Set cells in no particular order.
+    pub fn iter_mut(&'_ mut self) -> impl Iterator<Item = MutableGridElement<'_, T>> {
+        self._cells
+            .iter_mut()
+            .map(|(position, value)| MutableGridElement {
+                position: *position,
+                value,
+            })
+    }
+
+    /// Converts to a dense grid covering the bounds, cells that are not set become `T::default()`.
+    /// Returns the grid and the coordinate of its top left cell.
+    pub fn to_grid(&self) -> (Grid<T>, Coord)
+    where
+        T: Default + Clone,
+    {
+        let Some(bounds) = self._bounds else {
+            return (Grid::new(0, 0), Coord::ORIGIN);
+        };
+
+        let origin = bounds.top_corner();
+        let mut grid = Grid::new(bounds.length(), bounds.width());
+        for (position, value) in &self._cells {
+            grid[*position - origin] = value.clone();
+        }
+
+        (grid, origin)
+    }
+
+    /// Draws the bounds row by row, `func` receives the cell at every coordinate if it is set.
+    pub fn render(&self, mut func: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(bounds) = self._bounds else {
+            return String::new();
+        };
+
+        bounds
+            .iter_horizontal_lines()
+            .map(|line| {
+                (line.start.x..=line.end.x)
+                    .map(|x| func(self.get(&Coord::new(x, line.start.y))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T: Copy> SparseGrid<T> {
+    /// Set cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = GridElement<T>> {
+        self._cells.iter().map(|(position, value)| GridElement {
+            position: *position,
+            value: *value,
+        })
+    }
+}
+
+impl<T> Index<Coord> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(&index)
+            .unwrap_or_else(|| panic!("no cell at {index}"))
+    }
+}
+
+impl<T> IndexMut<Coord> for SparseGrid<T> {
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        self.get_mut(&index)
+            .unwrap_or_else(|| panic!("no cell at {index}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expanding_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Coord::new(2, 1), 'a');
+        grid.insert(Coord::new(-3, 4), 'b');
+        assert_eq!(grid.insert(Coord::new(2, 1), 'c'), Some('a'));
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.width(), 6);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid[Coord::new(2, 1)], 'c');
+        assert_eq!(grid.get(&Coord::new(0, 0)), None);
+
+        grid.remove(Coord::new(-3, 4));
+        assert!(!grid.contains(&Coord::new(-3, 4)));
+        assert_eq!(grid.width(), 6);
+    }
+
+    #[test]
+    fn test_get_or_insert_default() {
+        let mut grid = SparseGrid::<u32>::new();
+        *grid.get_or_insert_default(Coord::new(-1, -1)) += 1;
+        *grid.get_or_insert_default(Coord::new(-1, -1)) += 1;
+        *grid.get_or_insert_default(Coord::new(1, 0)) += 1;
+
+        assert_eq!(grid[Coord::new(-1, -1)], 2);
+        assert_eq!(grid.iter().map(|it| it.value).sum::<u32>(), 3);
+        assert_eq!(
+            grid.bounds(),
+            Some(PositionalRectangle::new(
+                Coord::new(-1, -1),
+                Coord::new(1, 0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_to_grid() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Coord::new(-1, 0), 1);
+        grid.insert(Coord::new(1, 1), 2);
+
+        let (dense, origin) = grid.to_grid();
+        assert_eq!(origin, Coord::new(-1, 0));
+        assert_eq!(dense.width(), 3);
+        assert_eq!(dense.height(), 2);
+        assert_eq!(dense[Coord::new(0, 0)], 1);
+        assert_eq!(dense[Coord::new(2, 1)], 2);
+        assert_eq!(dense[Coord::new(1, 0)], 0);
+    }
+
+    #[test]
+    fn test_render() {
+        let picture = "#..\n.#.\n..#";
+        let grid = SparseGrid::parse(picture, |c| (c == '#').then_some(()));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(
+            grid.render(|cell| if cell.is_some() { '#' } else { '.' }),
+            picture
+        );
+    }
+}